@@ -1,13 +1,25 @@
 use super::{disc::Disc, track::Track};
 use crate::{
-    image::{Image, LoadWithCacheError},
+    articles::Articles,
+    artists::ArtistStyle,
+    artwork::{self, Artwork, ArtworkKind},
+    carry::{self, Inherited, Position},
+    credits::Credits,
+    custom::Custom,
+    date::Date,
+    file_safety::FileSafety,
+    image::{
+        self as img, CoverSettings, Image, ImageChecks, ImageWarning, LoadWithCacheError, Shape,
+    },
+    profile::{ExportFormat, Profile},
     raw,
+    release::Release,
     text::Text,
 };
 use once_cell::sync::OnceCell;
 use std::{
     borrow::Cow,
-    fmt,
+    fmt, fs,
     path::{Path, PathBuf},
 };
 
@@ -17,6 +29,11 @@ pub struct Album {
     path: PathBuf,
     cover: OnceCell<Option<Image>>,
     cover_vw: OnceCell<Option<Image>>,
+    artwork: OnceCell<Vec<Artwork>>,
+    artwork_vw: OnceCell<Vec<Artwork>>,
+    inherited: OnceCell<Vec<Vec<Inherited>>>,
+    default_articles: Articles,
+    file_safety: FileSafety,
 }
 
 impl Album {
@@ -26,9 +43,26 @@ impl Album {
             path: path.into(),
             cover: OnceCell::new(),
             cover_vw: OnceCell::new(),
+            artwork: OnceCell::new(),
+            artwork_vw: OnceCell::new(),
+            inherited: OnceCell::new(),
+            default_articles: Articles::default(),
+            file_safety: FileSafety::default(),
         }
     }
 
+    /// Set the rules used to make the album's filenames safe.
+    pub fn with_file_safety(mut self, file_safety: FileSafety) -> Self {
+        self.file_safety = file_safety;
+        self
+    }
+
+    /// Set the articles used when the album doesn't define its own, such as a library's.
+    pub fn with_default_articles(mut self, articles: Articles) -> Self {
+        self.default_articles = articles;
+        self
+    }
+
     pub fn load<P: Into<PathBuf>>(path: P) -> Result<Self, AlbumLoadError> {
         use std::fs::File;
         let path = path.into();
@@ -58,38 +92,131 @@ impl Album {
         &self.album
     }
 
+    /// Gets the album's manifest to change it. Covers that have already been loaded aren't
+    /// updated.
+    pub fn raw_mut(&mut self) -> &mut raw::Album {
+        self.inherited.take();
+        &mut self.album
+    }
+
+    /// Gets what a track inherits from earlier tracks, its disc and the album.
+    ///
+    /// This is resolved for every track at once the first time it's needed.
+    pub fn inherited(&self, disc_number: usize, track_number: usize) -> Inherited {
+        let inherited = self.inherited.get_or_init(|| carry::resolve(&self.album));
+        inherited[disc_number - 1][track_number - 1]
+    }
+
+    /// Gets a track from the manifest by its position.
+    pub fn raw_track(&self, (disc, track): Position) -> &raw::Track {
+        &self.album.discs[disc].tracks()[track]
+    }
+
     pub fn title(&self) -> &Text {
         &self.album.title
     }
 
+    /// Gets the title in the language variant of an export format's profile.
+    pub fn title_for(&self, format: ExportFormat) -> Cow<'_, Text> {
+        self.profile(format).text(self.title())
+    }
+
     pub fn artists(&self) -> &[Text] {
         &self.album.artists
     }
 
-    pub fn artist(&self) -> Cow<Text> {
+    pub fn artist(&self) -> Cow<'_, Text> {
         self.album.artist()
     }
 
-    pub fn year(&self) -> Option<raw::AlbumYear> {
-        self.album.year
+    /// Gets how the album's artists are displayed and written to tags.
+    pub fn artist_style(&self) -> ArtistStyle {
+        self.album.artist_style
+    }
+
+    /// Returns if the album collects tracks by different artists.
+    pub fn is_compilation(&self) -> bool {
+        self.album.compilation
+    }
+
+    /// Gets the folder the album is exported to under a library's root: a folder named after
+    /// its artist, or the compilations folder if it's a compilation.
+    pub fn library_path<P>(&self, root: P, compilations: &str, safety: FileSafety) -> PathBuf
+    where
+        P: Into<PathBuf>,
+    {
+        let mut path = root.into();
+        if self.is_compilation() {
+            path.push(&*Text::from(compilations.to_string()).file_safe_for(safety));
+        } else {
+            path.push(&*self.artist().file_safe_for(safety));
+        }
+        path.push(&*self.title().file_safe_for(safety));
+        path
+    }
+
+    pub fn date(&self) -> Option<raw::AlbumDate> {
+        self.album.date
+    }
+
+    pub fn original_date(&self) -> Option<Date> {
+        self.album.original_date
     }
 
     pub fn genre(&self) -> Option<&Text> {
         self.album.genre()
     }
 
+    /// Gets the language the album is sung in, if it's set.
+    pub fn language(&self) -> Option<&str> {
+        self.album.language.as_deref()
+    }
+
+    /// Gets the people credited on every track, unless a disc or track overrides them.
+    pub fn credits(&self) -> &Credits {
+        &self.album.credits
+    }
+
+    /// Gets the custom fields on every track, unless a disc or track removes them.
+    pub fn custom(&self) -> &Custom {
+        &self.album.custom
+    }
+
+    /// Gets details about the album's release, like its label and catalog number.
+    pub fn release(&self) -> &Release {
+        &self.album.release
+    }
+
+    /// Gets the rules used to make the album's filenames safe.
+    pub fn file_safety(&self) -> FileSafety {
+        self.file_safety
+    }
+
+    /// Gets the articles moved to the end of names when sorting.
+    pub fn articles(&self) -> &Articles {
+        self.album
+            .articles
+            .as_ref()
+            .unwrap_or(&self.default_articles)
+    }
+
+    /// Gets a sortable, filename safe version of a text, using the album's articles.
+    pub fn sortable_file_safe<'b>(&self, text: &'b Text) -> Cow<'b, str> {
+        text.sortable_file_safe_with(self.articles())
+    }
+
     pub fn num_discs(&self) -> usize {
         self.album.num_discs()
     }
 
-    pub fn disc(&self, disc_number: usize) -> Option<Disc> {
+    pub fn disc(&self, disc_number: usize) -> Option<Disc<'_>> {
         self.album
             .discs
             .get(disc_number - 1)
             .map(|disc| Disc::new(self, disc, disc_number))
     }
 
-    pub fn discs(&self) -> impl Iterator<Item = Disc> {
+    pub fn discs(&self) -> impl Iterator<Item = Disc<'_>> {
         self.album
             .discs
             .iter()
@@ -101,10 +228,14 @@ impl Album {
         self.album.num_tracks()
     }
 
-    pub fn tracks(&self) -> Tracks {
+    pub fn tracks(&self) -> Tracks<'_> {
         Tracks::new(self)
     }
 
+    pub fn profile(&self, format: ExportFormat) -> Profile {
+        self.album.profiles.resolve(format)
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -113,6 +244,10 @@ impl Album {
         self.path().join("extras")
     }
 
+    pub fn lyrics_path(&self) -> PathBuf {
+        self.extras_path().join("lyrics")
+    }
+
     pub fn image_path(&self) -> PathBuf {
         let mut path = self.extras_path();
         path.push("images");
@@ -125,43 +260,183 @@ impl Album {
         path
     }
 
-    pub fn covers_path(&self) -> PathBuf {
+    /// Gets the cache folder for covers processed with the given settings.
+    ///
+    /// Each set of settings gets its own folder, so changing them never picks up stale covers.
+    pub fn covers_path_for(&self, settings: &CoverSettings) -> PathBuf {
         let mut path = self.cache_path();
         path.push("covers");
+        path.push(settings.cache_name());
         path
     }
 
+    pub fn covers_path(&self) -> PathBuf {
+        self.covers_path_for(&self.profile(ExportFormat::Full).cover)
+    }
+
     pub fn covers_vw_path(&self) -> PathBuf {
-        let mut path = self.cache_path();
-        path.push("covers-vw");
-        path
+        self.covers_path_for(&self.profile(ExportFormat::Vw).cover)
     }
 
-    fn get_cover<'a, P, F>(
+    fn get_cover<'a>(
         &'a self,
         cover: &'a OnceCell<Option<Image>>,
-        covers_path: P,
-        transform: F,
-    ) -> Result<Option<&'a Image>, LoadWithCacheError>
-    where
-        P: AsRef<Path>,
-        F: Fn(image::DynamicImage) -> Result<Image, image::ImageError>,
-    {
+        format: ExportFormat,
+    ) -> Result<Option<&'a Image>, LoadWithCacheError> {
         cover
             .get_or_try_init(|| {
-                Image::try_load_with_cache(self.image_path(), covers_path, "Front Cover", transform)
+                let settings = self.profile(format).cover;
+                Image::try_load_with_cache(
+                    self.image_path(),
+                    self.covers_path_for(&settings),
+                    "Front Cover",
+                    |raw| img::transform_cover(raw, &settings),
+                )
             })
             .map(Option::as_ref)
     }
 
+    /// Gets the path of the album's source front cover, if it has one.
+    pub fn cover_file(&self) -> Option<PathBuf> {
+        img::find_with_name(&self.image_path(), "Front Cover")
+    }
+
     pub fn cover(&self) -> Result<Option<&Image>, LoadWithCacheError> {
-        use crate::image::transform_image;
-        self.get_cover(&self.cover, self.covers_path(), transform_image)
+        self.get_cover(&self.cover, ExportFormat::Full)
     }
 
     pub fn cover_vw(&self) -> Result<Option<&Image>, LoadWithCacheError> {
-        use crate::image::transform_image_vw;
-        self.get_cover(&self.cover_vw, self.covers_vw_path(), transform_image_vw)
+        self.get_cover(&self.cover_vw, ExportFormat::Vw)
+    }
+
+    fn get_artwork<'a>(
+        &'a self,
+        artwork: &'a OnceCell<Vec<Artwork>>,
+        format: ExportFormat,
+    ) -> Result<&'a [Artwork], LoadWithCacheError> {
+        artwork
+            .get_or_try_init(|| {
+                let profile = self.profile(format);
+                artwork::find_artwork(self.image_path())?
+                    .into_iter()
+                    .filter(|(kind, _, _)| profile.includes(*kind))
+                    .map(|(kind, name, disc)| {
+                        // Only covers get cropped or padded; booklets and photos keep their shape.
+                        let mut settings = profile.cover.clone();
+                        if !kind.is_cover() {
+                            settings.shape = Shape::Fit;
+                        }
+
+                        let image = Image::load_with_cache(
+                            self.image_path(),
+                            self.covers_path_for(&settings),
+                            &name,
+                            |raw| img::transform_cover(raw, &settings),
+                        )?;
+
+                        Ok(Artwork {
+                            kind,
+                            name,
+                            disc,
+                            image,
+                        })
+                    })
+                    .collect()
+            })
+            .map(Vec::as_slice)
+    }
+
+    /// Gets the artwork other than front covers, filtered by the full profile.
+    pub fn artwork(&self) -> Result<&[Artwork], LoadWithCacheError> {
+        self.get_artwork(&self.artwork, ExportFormat::Full)
+    }
+
+    /// Gets the artwork other than front covers, filtered by the car profile.
+    pub fn artwork_vw(&self) -> Result<&[Artwork], LoadWithCacheError> {
+        self.get_artwork(&self.artwork_vw, ExportFormat::Vw)
+    }
+
+    /// Finds texts that have no usable ASCII representation, with a description of where they are.
+    pub fn texts_without_ascii(&self) -> Vec<(String, Text)> {
+        let mut found = Vec::new();
+        let mut check = |location: String, text: &Text| {
+            if text.has_empty_ascii() {
+                found.push((location, text.clone()));
+            }
+        };
+
+        check(String::from("album title"), self.title());
+        for artist in self.artists() {
+            check(String::from("album artist"), artist);
+        }
+        for track in self.tracks() {
+            let location = format!(
+                "disc {} track {}",
+                track.disc().disc_number,
+                track.track_number
+            );
+            check(format!("{} title", location), track.title());
+            if track.album_artists().is_some() {
+                for artist in track.artists() {
+                    check(format!("{} artist", location), artist);
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Checks the quality of every source image, returning warnings with the file they're for.
+    ///
+    /// Anything that isn't a booklet page or artist photo is treated as a cover.
+    pub fn check_images(
+        &self,
+        checks: &ImageChecks,
+    ) -> Result<Vec<(String, ImageWarning)>, LoadWithCacheError> {
+        let images = self.image_path();
+        if !images.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = Vec::new();
+        let entries = fs::read_dir(&images).map_err(LoadWithCacheError::CouldntReadImagesFolder)?;
+        for entry in entries {
+            let path = entry
+                .map_err(LoadWithCacheError::CouldntReadImagesFolder)?
+                .path();
+            let is_image = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| img::EXTENSIONS.contains(&e.to_lowercase().as_str()))
+                .unwrap_or(false);
+            if is_image {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut warnings = Vec::new();
+        for path in paths {
+            let file = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let stem = path
+                .file_stem()
+                .map(|n| n.to_string_lossy())
+                .unwrap_or_default();
+            let is_cover = !matches!(
+                ArtworkKind::classify(&stem),
+                Some((ArtworkKind::Booklet | ArtworkKind::Artist, _))
+            );
+
+            match img::check_image(&path, checks, is_cover) {
+                Ok(found) => warnings.extend(found.into_iter().map(|w| (file.clone(), w))),
+                Err(e) => warnings.push((file, ImageWarning::Unreadable(e.to_string()))),
+            }
+        }
+
+        Ok(warnings)
     }
 }
 
@@ -226,3 +501,27 @@ impl<'a> Iterator for Tracks<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compilations_are_filed_under_compilations_folder() {
+        let album = raw::Album::new("Now 42").with_artists(vec![Text::from("Someone")]);
+        let album = Album::new(album, PathBuf::from("."));
+        assert_eq!(
+            Path::new("root/Someone/Now 42"),
+            album.library_path("root", "Compilations", FileSafety::Windows)
+        );
+
+        let album = raw::Album::new("Now 42")
+            .with_artists(vec![Text::from(raw::VARIOUS_ARTISTS)])
+            .with_compilation(true);
+        let album = Album::new(album, PathBuf::from("."));
+        assert_eq!(
+            Path::new("root/Various/Now 42"),
+            album.library_path("root", "Various", FileSafety::Windows)
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! Articles that are moved to the end of names when sorting.
+
+use serde::{de, ser, Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// A language whose articles can be recognized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "it")]
+    Italian,
+    #[serde(rename = "pt")]
+    Portuguese,
+    #[serde(rename = "nl")]
+    Dutch,
+}
+
+impl Language {
+    /// Every supported language.
+    pub const ALL: [Language; 7] = [
+        Language::English,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Italian,
+        Language::Portuguese,
+        Language::Dutch,
+    ];
+
+    /// Gets the language's code.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::Dutch => "nl",
+        }
+    }
+
+    /// Gets the language's articles, in lowercase.
+    ///
+    /// Articles ending in an apostrophe are elided, and attach directly to the next word.
+    pub fn articles(self) -> &'static [&'static str] {
+        match self {
+            Language::English => &["a", "an", "the"],
+            Language::German => &["der", "die", "das", "ein", "eine"],
+            Language::French => &["le", "la", "les", "l'", "un", "une"],
+            Language::Spanish => &["el", "la", "los", "las", "un", "una"],
+            Language::Italian => &["il", "lo", "la", "i", "gli", "le", "l'", "un", "uno", "una"],
+            Language::Portuguese => &["o", "a", "os", "as", "um", "uma"],
+            Language::Dutch => &["de", "het", "een"],
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .into_iter()
+            .find(|l| l.code().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown language \"{}\"", s))
+    }
+}
+
+/// The set of articles recognized when sorting.
+///
+/// Defaults to English.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Articles {
+    languages: Vec<Language>,
+    custom: Vec<String>,
+}
+
+impl Default for Articles {
+    fn default() -> Self {
+        Self::new(vec![Language::English])
+    }
+}
+
+impl Articles {
+    /// Create a set of articles from languages.
+    pub fn new(languages: Vec<Language>) -> Self {
+        Self {
+            languages,
+            custom: Vec::new(),
+        }
+    }
+
+    /// Add custom articles, which are matched ignoring case.
+    pub fn with_custom(mut self, custom: Vec<String>) -> Self {
+        self.custom = custom;
+        self
+    }
+
+    pub fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    pub fn custom(&self) -> &[String] {
+        &self.custom
+    }
+
+    fn all(&self) -> impl Iterator<Item = &str> {
+        self.languages
+            .iter()
+            .flat_map(|l| l.articles().iter().copied())
+            .chain(self.custom.iter().map(String::as_str))
+    }
+
+    /// Splits an initial article from a string.
+    ///
+    /// Returns a pair of the article and the rest of the string, or None if the string didn't
+    /// start with an article. The longest matching article is used.
+    ///
+    /// ```rust
+    /// # use maestro::articles::{Articles, Language};
+    /// let articles = Articles::new(vec![Language::German, Language::French]);
+    /// assert_eq!(articles.split("Die Arzte"), Some(("Die", "Arzte")));
+    /// assert_eq!(articles.split("L'Affaire"), Some(("L'", "Affaire")));
+    /// assert_eq!(articles.split("Diese Arzte"), None);
+    /// ```
+    pub fn split<'a>(&self, s: &'a str) -> Option<(&'a str, &'a str)> {
+        self.all()
+            .filter_map(|article| {
+                let prefix = s.get(..article.len())?;
+                if !prefix.eq_ignore_ascii_case(article) {
+                    return None;
+                }
+
+                let rest = if article.ends_with('\'') {
+                    &s[article.len()..]
+                } else {
+                    s[article.len()..].strip_prefix(' ')?
+                };
+                (!rest.is_empty()).then_some((prefix, rest))
+            })
+            .max_by_key(|(article, _)| article.len())
+    }
+
+    /// Moves an initial article to the end of a string, after a comma.
+    ///
+    /// ```rust
+    /// # use maestro::articles::{Articles, Language};
+    /// let articles = Articles::new(vec![Language::Spanish]);
+    /// assert_eq!("Lobos, Los", articles.sortable("Los Lobos"));
+    /// assert_eq!("The Who", articles.sortable("The Who"));
+    /// ```
+    pub fn sortable(&self, s: &str) -> String {
+        match self.split(s) {
+            Some((article, rest)) => format!("{}, {}", rest, article),
+            None => s.to_string(),
+        }
+    }
+}
+
+impl FromStr for Articles {
+    type Err = String;
+
+    /// Parses a comma separated list of language codes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|l| l.trim().parse())
+            .collect::<Result<_, _>>()
+            .map(Articles::new)
+    }
+}
+
+impl Serialize for Articles {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::SerializeStruct;
+
+        if self.custom.is_empty() {
+            return self.languages.serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_struct("Articles", 2)?;
+        state.serialize_field("languages", &self.languages)?;
+        state.serialize_field("custom", &self.custom)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Articles {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Articles;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of languages or an articles definition")
+            }
+
+            fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let languages =
+                    Deserialize::deserialize(de::value::SeqAccessDeserializer::new(seq))?;
+                Ok(Articles::new(languages))
+            }
+
+            fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+            where
+                M: de::MapAccess<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(field_identifier, rename_all = "lowercase")]
+                enum Fields {
+                    Languages,
+                    Custom,
+                }
+
+                let mut languages = None;
+                let mut custom = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Fields::Languages => field!(map, languages),
+                        Fields::Custom => field!(map, custom),
+                    }
+                }
+
+                Ok(Articles::new(languages.unwrap_or_default())
+                    .with_custom(custom.unwrap_or_default()))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_is_english() {
+        let articles = Articles::default();
+        assert_eq!(articles.split("The Beatles"), Some(("The", "Beatles")));
+        assert_eq!(articles.split("An Album"), Some(("An", "Album")));
+        assert_eq!(articles.split("Die Arzte"), None);
+    }
+
+    #[test]
+    fn split_preserves_capitalization() {
+        assert_eq!(
+            Articles::default().split("THe titLe"),
+            Some(("THe", "titLe"))
+        );
+    }
+
+    #[test]
+    fn split_only_removes_first_space() {
+        assert_eq!(
+            Articles::default().split("the   title"),
+            Some(("the", "  title"))
+        );
+    }
+
+    #[test]
+    fn split_doesnt_split_if_no_space() {
+        assert_eq!(Articles::default().split("the_title"), None);
+    }
+
+    #[test]
+    fn article_alone_isnt_split() {
+        assert_eq!(Articles::default().split("The "), None);
+        assert_eq!(Articles::default().split("The"), None);
+    }
+
+    #[test]
+    fn longest_article_is_used() {
+        let articles = Articles::new(vec![Language::Spanish, Language::French]);
+        assert_eq!(articles.split("Las Ketchup"), Some(("Las", "Ketchup")));
+        assert_eq!(
+            Articles::new(vec![Language::Italian]).split("Gli Uomini"),
+            Some(("Gli", "Uomini"))
+        );
+    }
+
+    #[test]
+    fn custom_articles_are_split() {
+        let articles = Articles::new(vec![]).with_custom(vec![String::from("y'all")]);
+        assert_eq!(articles.split("Y'all Band"), Some(("Y'all", "Band")));
+    }
+
+    #[test]
+    fn languages_are_parsed_from_list() {
+        let articles: Articles = serde_yaml::from_str("[de, fr]").unwrap();
+        assert_eq!(
+            Articles::new(vec![Language::German, Language::French]),
+            articles
+        );
+        assert_eq!(Ok(articles), "de, fr".parse());
+    }
+
+    #[test]
+    fn custom_articles_are_parsed_from_map() {
+        let articles: Articles = serde_yaml::from_str("{languages: [es], custom: [ye]}").unwrap();
+        assert_eq!(
+            Articles::new(vec![Language::Spanish]).with_custom(vec![String::from("ye")]),
+            articles
+        );
+    }
+
+    #[test]
+    fn unknown_language_isnt_parsed() {
+        assert!(serde_yaml::from_str::<Articles>("[xx]").is_err());
+        assert!("en,xx".parse::<Articles>().is_err());
+    }
+}
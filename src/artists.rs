@@ -0,0 +1,172 @@
+//! How lists of artists are displayed and written to tags.
+
+use crate::text::{Text, COMMA_SEP, EMPTY_TEXT};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// How a list of artists is joined for display.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JoinStyle {
+    /// "A, B, C".
+    #[default]
+    Comma,
+
+    /// "A & B", or "A, B & C".
+    Ampersand,
+
+    /// "A and B", or "A, B and C".
+    And,
+}
+
+impl JoinStyle {
+    /// Joins a list of artists.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::{artists::JoinStyle, Text};
+    /// let artists = [Text::from("A"), Text::from("B"), Text::from("C")];
+    /// assert_eq!("A, B, C", JoinStyle::Comma.join(&artists).value());
+    /// assert_eq!("A & B", JoinStyle::Ampersand.join(&artists[..2]).value());
+    /// assert_eq!("A, B and C", JoinStyle::And.join(&artists).value());
+    /// ```
+    pub fn join(self, artists: &[Text]) -> Cow<'_, Text> {
+        let last_sep = match self {
+            JoinStyle::Comma => COMMA_SEP,
+            JoinStyle::Ampersand => Text::from(" & "),
+            JoinStyle::And => Text::from(" and "),
+        };
+
+        match artists.split_last() {
+            None => Cow::Owned(EMPTY_TEXT),
+            Some((last, [])) => Cow::Borrowed(last),
+            Some((last, rest)) => {
+                let mut res = Text::join(rest, &COMMA_SEP);
+                res += &last_sep;
+                res += last;
+                Cow::Owned(res)
+            }
+        }
+    }
+}
+
+/// How artist frames are written to tags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtistFrames {
+    /// A single value, joined with the join style.
+    #[default]
+    Joined,
+
+    /// One value per artist, separated by nulls as ID3v2.4 allows.
+    Multiple,
+
+    /// One value per artist, separated by semicolons for players that only read ID3v2.3.
+    Semicolon,
+}
+
+/// The separator [`ArtistFrames::Semicolon`] writes between artists.
+pub(crate) const SEMICOLON: &str = "; ";
+
+/// How an album's artists are displayed and written to tags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArtistStyle {
+    /// How artists are joined for display.
+    pub join: JoinStyle,
+
+    /// How artist frames are written.
+    pub frames: ArtistFrames,
+}
+
+impl ArtistStyle {
+    /// Returns if nothing has been changed from the default style.
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Gets the contents of an artist frame for a list of artists.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::{artists::{ArtistFrames, ArtistStyle}, Text};
+    /// let artists = [Text::from("Crosby, Stills, Nash & Young"), Text::from("B")];
+    /// let style = ArtistStyle {
+    ///     frames: ArtistFrames::Multiple,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!("Crosby, Stills, Nash & Young\0B", style.frame_value(&artists));
+    /// ```
+    pub fn frame_value(&self, artists: &[Text]) -> String {
+        let sep = match self.frames {
+            ArtistFrames::Joined => return self.join.join(artists).value().to_string(),
+            ArtistFrames::Multiple => "\0",
+            ArtistFrames::Semicolon => SEMICOLON,
+        };
+        artists
+            .iter()
+            .map(Text::value)
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_artist_is_same() {
+        let artists = [Text::from(("foo", "bar"))];
+        for style in [JoinStyle::Comma, JoinStyle::Ampersand, JoinStyle::And] {
+            assert_eq!(Cow::Borrowed(&artists[0]), style.join(&artists));
+        }
+    }
+
+    #[test]
+    fn joined_ascii_is_joined() {
+        let artists = [Text::from(("a", "b")), Text::from("c"), Text::from("d")];
+        assert_eq!(
+            Text::from(("a, c & d", "b, c & d")),
+            JoinStyle::Ampersand.join(&artists).into_owned()
+        );
+    }
+
+    #[test]
+    fn semicolon_frames_are_separated() {
+        let style = ArtistStyle {
+            join: JoinStyle::And,
+            frames: ArtistFrames::Semicolon,
+        };
+        assert_eq!(
+            "A; B; C",
+            style.frame_value(&[Text::from("A"), Text::from("B"), Text::from("C")])
+        );
+    }
+
+    #[test]
+    fn joined_frames_use_join_style() {
+        let style = ArtistStyle {
+            join: JoinStyle::And,
+            frames: ArtistFrames::Joined,
+        };
+        assert_eq!(
+            "A and B",
+            style.frame_value(&[Text::from("A"), Text::from("B")])
+        );
+    }
+
+    #[test]
+    fn style_is_parsed() {
+        let style: ArtistStyle = serde_yaml::from_str("{join: and, frames: multiple}").unwrap();
+        assert_eq!(
+            ArtistStyle {
+                join: JoinStyle::And,
+                frames: ArtistFrames::Multiple,
+            },
+            style
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Artwork other than the front cover.
+
+use crate::image::{self as img, Image, LoadWithCacheError};
+use id3::frame::PictureType;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// A kind of artwork that can be embedded in tracks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtworkKind {
+    /// The front cover of the album, disc or track.
+    FrontCover,
+
+    /// The back cover of the album, named "Back Cover".
+    BackCover,
+
+    /// A picture of a disc, named "Disc 1 Media", or "Media" for every disc.
+    Media,
+
+    /// A page of the booklet, named "Booklet 01", "Booklet 02" and so on.
+    Booklet,
+
+    /// A picture of the artist, named "Artist".
+    Artist,
+}
+
+impl ArtworkKind {
+    /// Every kind of artwork.
+    pub const ALL: [ArtworkKind; 5] = [
+        ArtworkKind::FrontCover,
+        ArtworkKind::BackCover,
+        ArtworkKind::Media,
+        ArtworkKind::Booklet,
+        ArtworkKind::Artist,
+    ];
+
+    /// Gets the ID3 picture type used for the kind of artwork.
+    pub fn picture_type(self) -> PictureType {
+        match self {
+            Self::FrontCover => PictureType::CoverFront,
+            Self::BackCover => PictureType::CoverBack,
+            Self::Media => PictureType::Media,
+            Self::Booklet => PictureType::Leaflet,
+            Self::Artist => PictureType::Artist,
+        }
+    }
+
+    /// Gets the kind of artwork a picture type is embedded as, if any.
+    pub fn from_picture_type(picture_type: PictureType) -> Option<Self> {
+        match picture_type {
+            PictureType::CoverFront => Some(Self::FrontCover),
+            PictureType::CoverBack => Some(Self::BackCover),
+            PictureType::Media => Some(Self::Media),
+            PictureType::Leaflet => Some(Self::Booklet),
+            PictureType::Artist | PictureType::LeadArtist => Some(Self::Artist),
+            _ => None,
+        }
+    }
+
+    /// Returns if the artwork is a cover, and so should be shaped like one.
+    pub fn is_cover(self) -> bool {
+        matches!(self, Self::FrontCover | Self::BackCover | Self::Media)
+    }
+
+    /// Classifies an image by its name (without extension.)
+    ///
+    /// Returns the kind of artwork and the number of the disc it belongs to, or `None` if the
+    /// name doesn't follow any artwork convention. Disc and track covers aren't classified, since
+    /// their names depend on the album.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::artwork::ArtworkKind;
+    /// assert_eq!(Some((ArtworkKind::BackCover, None)), ArtworkKind::classify("Back Cover"));
+    /// assert_eq!(Some((ArtworkKind::Media, Some(2))), ArtworkKind::classify("Disc 2 Media"));
+    /// assert_eq!(Some((ArtworkKind::Booklet, None)), ArtworkKind::classify("Booklet 03"));
+    /// assert_eq!(None, ArtworkKind::classify("Disc 2"));
+    /// ```
+    pub fn classify(name: &str) -> Option<(ArtworkKind, Option<usize>)> {
+        match name {
+            "Front Cover" => return Some((Self::FrontCover, None)),
+            "Back Cover" => return Some((Self::BackCover, None)),
+            "Media" => return Some((Self::Media, None)),
+            "Artist" => return Some((Self::Artist, None)),
+            _ => {}
+        }
+
+        fn is_number(s: &str) -> bool {
+            !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+        }
+
+        if let Some(page) = name.strip_prefix("Booklet ") {
+            return is_number(page).then_some((Self::Booklet, None));
+        }
+
+        let disc = name.strip_prefix("Disc ")?.strip_suffix(" Media")?;
+        if !is_number(disc) {
+            return None;
+        }
+        Some((Self::Media, disc.parse().ok()))
+    }
+}
+
+/// A piece of artwork found in the album's images folder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Artwork {
+    /// The kind of artwork.
+    pub kind: ArtworkKind,
+
+    /// The name of the image, which is also used as its description.
+    pub name: String,
+
+    /// The disc the artwork belongs to, or `None` if it belongs to every disc.
+    pub disc: Option<usize>,
+
+    /// The processed image.
+    pub image: Image,
+}
+
+impl Artwork {
+    /// Returns if the artwork should be embedded in tracks on a disc.
+    pub fn is_for_disc(&self, disc_number: usize) -> bool {
+        self.disc.map(|d| d == disc_number).unwrap_or(true)
+    }
+
+    /// Creates an ID3 picture frame for the artwork.
+    pub fn id3_picture(&self) -> id3::frame::Picture {
+        id3::frame::Picture {
+            mime_type: self.image.format.mime().to_string(),
+            picture_type: self.kind.picture_type(),
+            description: self.name.clone(),
+            data: self.image.data.clone(),
+        }
+    }
+}
+
+/// Finds the names of all the images in a folder that follow artwork conventions.
+///
+/// Front covers are skipped, since they're looked up separately. The names are sorted so that
+/// booklet pages stay in order.
+pub fn find_artwork<P: AsRef<Path>>(
+    images: P,
+) -> Result<Vec<(ArtworkKind, String, Option<usize>)>, LoadWithCacheError> {
+    let images = images.as_ref();
+    if !images.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut found = Vec::new();
+    let entries = fs::read_dir(images).map_err(LoadWithCacheError::CouldntReadImagesFolder)?;
+    for entry in entries {
+        let path = entry
+            .map_err(LoadWithCacheError::CouldntReadImagesFolder)?
+            .path();
+        let is_image = img::extension_index(&path).is_some();
+        let name = match path.file_stem().and_then(|s| s.to_str()) {
+            Some(name) if is_image => name,
+            _ => continue,
+        };
+
+        match ArtworkKind::classify(name) {
+            None | Some((ArtworkKind::FrontCover, _)) => {}
+            Some((kind, disc)) => found.push((kind, name.to_string(), disc)),
+        }
+    }
+
+    found.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+    found.dedup_by(|a, b| a.1 == b.1);
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_names_arent_classified() {
+        assert_eq!(None, ArtworkKind::classify("Some Song"));
+        assert_eq!(None, ArtworkKind::classify("Booklet"));
+        assert_eq!(None, ArtworkKind::classify("Booklet One"));
+        assert_eq!(None, ArtworkKind::classify("Disc A Media"));
+    }
+
+    #[test]
+    fn artwork_without_disc_is_for_every_disc() {
+        let artwork = Artwork {
+            kind: ArtworkKind::Media,
+            name: String::from("Media"),
+            disc: None,
+            image: Image::from_png(Vec::new()),
+        };
+        assert!(artwork.is_for_disc(1));
+        assert!(artwork.is_for_disc(2));
+    }
+
+    #[test]
+    fn artwork_with_disc_is_only_for_that_disc() {
+        let artwork = Artwork {
+            kind: ArtworkKind::Media,
+            name: String::from("Disc 2 Media"),
+            disc: Some(2),
+            image: Image::from_png(Vec::new()),
+        };
+        assert!(!artwork.is_for_disc(1));
+        assert!(artwork.is_for_disc(2));
+    }
+
+    #[test]
+    fn artwork_is_found_in_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "Front Cover.jpg",
+            "Booklet 02.png",
+            "Booklet 01.jpg",
+            "Back Cover.PNG",
+            "Disc 1 Media.Jpeg",
+            "Song.png",
+            "Artist.txt",
+        ] {
+            fs::write(dir.path().join(name), b"").unwrap();
+        }
+
+        let found = find_artwork(dir.path()).unwrap();
+        let names = found.iter().map(|f| f.1.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            vec!["Back Cover", "Disc 1 Media", "Booklet 01", "Booklet 02"],
+            names
+        );
+        assert_eq!(
+            Some(dir.path().join("Back Cover.PNG")),
+            img::find_with_name(dir.path(), "Back Cover")
+        );
+    }
+}
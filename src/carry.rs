@@ -0,0 +1,155 @@
+//! Values tracks inherit from earlier tracks, their disc or their album.
+
+use crate::{date::Date, raw};
+use serde::{Deserialize, Serialize};
+
+/// A field that carries over from the previous track when a track doesn't set it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Carried {
+    #[serde(alias = "year")]
+    Date,
+    Artists,
+    Genre,
+    Comment,
+}
+
+/// The position of a track in an album's manifest, as zero-based disc and track indexes.
+pub type Position = (usize, usize);
+
+/// The values a track ends up with once inheritance and carrying are applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Inherited {
+    pub date: Option<Date>,
+
+    /// The track whose artists are used, or None if the album's are.
+    pub artists: Option<Position>,
+
+    /// The track whose genre is used, or None if the album's is.
+    pub genre: Option<Position>,
+
+    /// The track whose comment is used, if any.
+    pub comment: Option<Position>,
+}
+
+/// Resolves what every track in an album inherits, in a single pass over its tracks.
+///
+/// A track's own value always wins. Otherwise, carried fields take the previous track's value,
+/// and everything else falls back to the disc's or album's. The first track has no previous
+/// track, so it falls back too.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::{carry::{self, Carried}, raw, Text};
+/// let album = raw::Album::new("foo")
+///     .with_carry(vec![Carried::Genre])
+///     .with_discs(vec![raw::Disc::from_tracks(vec![
+///         raw::Track::new("a"),
+///         raw::Track::new("b").with_genre(Text::from("Jazz")),
+///         raw::Track::new("c"),
+///     ])]);
+/// let inherited = carry::resolve(&album);
+/// assert_eq!(None, inherited[0][0].genre);
+/// assert_eq!(Some((0, 1)), inherited[0][2].genre);
+/// ```
+pub fn resolve(album: &raw::Album) -> Vec<Vec<Inherited>> {
+    let album_date = match album.date {
+        Some(raw::AlbumDate::Date(date)) => Some(date),
+        _ => None,
+    };
+    let carries = |field| album.carries(field);
+
+    let mut previous = Inherited::default();
+    let mut discs = Vec::with_capacity(album.discs.len());
+    for (d, disc) in album.discs.iter().enumerate() {
+        let mut tracks = Vec::with_capacity(disc.num_tracks());
+        for (t, track) in disc.tracks().iter().enumerate() {
+            let own = |has: bool| has.then_some((d, t));
+            let or_previous = |field, own: Option<Position>, previous: Option<Position>| {
+                own.or(previous.filter(|_| carries(field)))
+            };
+
+            let date = track.date.or(disc.date()).or_else(|| {
+                if carries(Carried::Date) {
+                    previous.date.or(album_date)
+                } else {
+                    album_date
+                }
+            });
+
+            let inherited = Inherited {
+                date,
+                artists: or_previous(
+                    Carried::Artists,
+                    own(track.artists().is_some()),
+                    previous.artists,
+                ),
+                genre: or_previous(Carried::Genre, own(track.genre().is_some()), previous.genre),
+                comment: or_previous(
+                    Carried::Comment,
+                    own(track.comments().is_some()),
+                    previous.comment,
+                ),
+            };
+            tracks.push(inherited);
+            previous = inherited;
+        }
+        discs.push(tracks);
+    }
+    discs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Text;
+
+    fn album(carry: Vec<Carried>) -> raw::Album {
+        raw::Album::new("foo")
+            .with_date(raw::AlbumDate::Date(Date::from_year(2000)))
+            .with_carry(carry)
+            .with_discs(vec![
+                raw::Disc::from_tracks(vec![
+                    raw::Track::new("a").with_artists(vec![Text::from("A")]),
+                    raw::Track::new("b").with_date(Date::from_year(2001)),
+                ]),
+                raw::Disc::from_tracks(vec![
+                    raw::Track::new("c").with_comment(Text::from("live")),
+                    raw::Track::new("d"),
+                ]),
+            ])
+    }
+
+    #[test]
+    fn uncarried_fields_fall_back_to_album() {
+        let inherited = resolve(&album(Vec::new()));
+        assert_eq!(Some(Date::from_year(2000)), inherited[1][0].date);
+        assert_eq!(None, inherited[0][1].artists);
+        assert_eq!(None, inherited[1][1].comment);
+    }
+
+    #[test]
+    fn carried_fields_cross_discs() {
+        let carry = vec![Carried::Date, Carried::Artists, Carried::Comment];
+        let inherited = resolve(&album(carry));
+        assert_eq!(Some(Date::from_year(2000)), inherited[0][0].date);
+        assert_eq!(Some(Date::from_year(2001)), inherited[1][1].date);
+        assert_eq!(Some((0, 0)), inherited[1][1].artists);
+        assert_eq!(Some((1, 0)), inherited[1][1].comment);
+    }
+
+    #[test]
+    fn year_carry_carries_dates() {
+        let album = album(Vec::new()).with_date(raw::AlbumDate::Carry);
+        let inherited = resolve(&album);
+        assert_eq!(None, inherited[0][0].date);
+        assert_eq!(Some(Date::from_year(2001)), inherited[1][0].date);
+    }
+
+    #[test]
+    fn carry_is_parsed() {
+        let carry: Vec<Carried> = serde_yaml::from_str("[year, genre, artists]").unwrap();
+        assert_eq!(vec![Carried::Date, Carried::Genre, Carried::Artists], carry);
+    }
+}
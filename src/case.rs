@@ -0,0 +1,409 @@
+//! Normalizing the capitalization of text.
+
+use crate::{raw, Text};
+use std::{fmt, str::FromStr};
+
+/// A capitalization style.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CaseStyle {
+    /// English title case, where small words like "of" and "the" are lowercase unless they
+    /// start or end the title.
+    #[default]
+    Title,
+
+    /// Sentence case, where only the first word of each sentence is capitalized.
+    Sentence,
+}
+
+/// Words that stay lowercase in the middle of a title.
+const SMALL_WORDS: [&str; 24] = [
+    "a", "an", "and", "as", "at", "but", "by", "en", "for", "from", "in", "into", "nor", "of",
+    "off", "on", "or", "per", "so", "the", "to", "via", "vs", "yet",
+];
+
+impl CaseStyle {
+    /// Every capitalization style.
+    pub const ALL: [CaseStyle; 2] = [CaseStyle::Title, CaseStyle::Sentence];
+
+    /// Gets the style's name.
+    pub fn name(self) -> &'static str {
+        match self {
+            CaseStyle::Title => "title",
+            CaseStyle::Sentence => "sentence",
+        }
+    }
+}
+
+impl fmt::Display for CaseStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for CaseStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CaseStyle::ALL
+            .into_iter()
+            .find(|c| c.name() == s)
+            .ok_or_else(|| format!("unknown capitalization style \"{}\"", s))
+    }
+}
+
+/// A change made to a text by normalization.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    /// A description of where the text is.
+    pub location: String,
+    pub old: Text,
+    pub new: Text,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}:", self.location)?;
+        writeln!(f, "- {}", self.old.value())?;
+        write!(f, "+ {}", self.new.value())
+    }
+}
+
+/// Applies a capitalization style to text.
+///
+/// Acronyms are kept as they are. These are uppercase words without vowels, like "MGMT",
+/// dotted initials like "R.E.M.", words with digits, Roman numerals, and any words given with
+/// [`Normalizer::with_acronyms`]. Words with capitals in the middle, like "iTunes" or
+/// "McCartney", are also kept.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Normalizer {
+    style: CaseStyle,
+    acronyms: Vec<String>,
+}
+
+impl Normalizer {
+    pub fn new(style: CaseStyle) -> Self {
+        Self {
+            style,
+            acronyms: Vec::new(),
+        }
+    }
+
+    /// Add words that are always written as given, ignoring case when matching.
+    pub fn with_acronyms(mut self, acronyms: Vec<String>) -> Self {
+        self.acronyms = acronyms;
+        self
+    }
+
+    /// Applies the style to a string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::case::{CaseStyle, Normalizer};
+    /// let title = Normalizer::new(CaseStyle::Title);
+    /// assert_eq!("The End of the World", title.apply("the END of THE world"));
+    /// assert_eq!("Live at the BBC", title.apply("live AT the BBC"));
+    ///
+    /// let sentence = Normalizer::new(CaseStyle::Sentence);
+    /// assert_eq!("The end of the world", sentence.apply("The End Of The World"));
+    /// ```
+    pub fn apply(&self, s: &str) -> String {
+        let words = s.split(' ').collect::<Vec<_>>();
+        let last = words.iter().rposition(|w| has_letters(w));
+        let mut starts_sentence = true;
+
+        let mut out = Vec::with_capacity(words.len());
+        for (i, word) in words.iter().enumerate() {
+            let (prefix, core, suffix) = split_punctuation(word);
+            if core.is_empty() {
+                out.push(word.to_string());
+                starts_sentence |= ends_clause(word);
+                continue;
+            }
+
+            let capitalize = starts_sentence
+                || prefix.contains(['(', '[', '"', '“'])
+                || match self.style {
+                    CaseStyle::Title => Some(i) == last || !is_small_word(core),
+                    CaseStyle::Sentence => false,
+                };
+
+            let core = core
+                .split_inclusive(['-', '/'])
+                .enumerate()
+                .map(|(j, part)| {
+                    let capitalize = match self.style {
+                        CaseStyle::Title => capitalize || j > 0,
+                        CaseStyle::Sentence => capitalize && j == 0,
+                    };
+                    self.apply_word(part, capitalize)
+                })
+                .collect::<String>();
+
+            out.push(format!("{}{}{}", prefix, core, suffix));
+            starts_sentence = ends_clause(suffix);
+        }
+
+        out.join(" ")
+    }
+
+    /// Normalizes a text, keeping any sort name and language variants, and normalizing an
+    /// overridden ASCII value the same way.
+    ///
+    /// Returns None if the text is locked or already normalized.
+    pub fn normalize(&self, text: &Text) -> Option<Text> {
+        if text.is_locked() {
+            return None;
+        }
+
+        let value = self.apply(text.value());
+        let ascii = text
+            .has_overridden_ascii()
+            .then(|| self.apply(text.ascii()));
+        if value == text.value() && ascii.as_deref().unwrap_or(text.ascii()) == text.ascii() {
+            return None;
+        }
+
+        Some(
+            Text::new(value, ascii)
+                .with_sort(text.sort_override().map(str::to_string))
+                .with_extras_from(text),
+        )
+    }
+
+    /// Normalizes the titles and artists of an album and its tracks, returning what changed.
+    pub fn normalize_album(&self, album: &mut raw::Album) -> Vec<Change> {
+        let mut changes = Vec::new();
+        let mut update = |location: String, text: &mut Text| {
+            if let Some(new) = self.normalize(text) {
+                let old = std::mem::replace(text, new.clone());
+                changes.push(Change { location, old, new });
+            }
+        };
+
+        update(String::from("album title"), &mut album.title);
+        for artist in &mut album.artists {
+            update(String::from("album artist"), artist);
+        }
+        for (d, disc) in album.discs.iter_mut().enumerate() {
+            for (t, track) in disc.tracks_mut().iter_mut().enumerate() {
+                let location = format!("disc {} track {}", d + 1, t + 1);
+                update(format!("{} title", location), &mut track.title);
+                for artist in track.artists_mut().into_iter().flatten() {
+                    update(format!("{} artist", location), artist);
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Capitalizes a single word, or lowercases it if it isn't an acronym.
+    fn apply_word(&self, word: &str, capitalize: bool) -> String {
+        let core = word.trim_end_matches(['-', '/']);
+        if let Some(acronym) = self.acronyms.iter().find(|a| a.eq_ignore_ascii_case(core)) {
+            return format!("{}{}", acronym, &word[core.len()..]);
+        }
+        if is_acronym(core) || has_mixed_capitals(core) {
+            return word.to_string();
+        }
+
+        let lower = word.to_lowercase();
+        if capitalize || is_pronoun_i(&lower) {
+            let mut chars = lower.chars();
+            match chars.next() {
+                Some(c) if c.is_alphabetic() => c.to_uppercase().chain(chars).collect(),
+                _ => lower,
+            }
+        } else {
+            lower
+        }
+    }
+}
+
+/// Splits a word into its leading punctuation, its content, and its trailing punctuation.
+fn split_punctuation(word: &str) -> (&str, &str, &str) {
+    let start = word
+        .find(|c: char| c.is_alphanumeric())
+        .unwrap_or(word.len());
+    let end = word
+        .rfind(|c: char| c.is_alphanumeric())
+        .map_or(start, |i| {
+            i + word[i..].chars().next().map_or(0, char::len_utf8)
+        });
+    (&word[..start], &word[start..end], &word[end..])
+}
+
+fn has_letters(word: &str) -> bool {
+    word.chars().any(char::is_alphanumeric)
+}
+
+/// Returns if punctuation ends a sentence or clause, so the next word is capitalized.
+fn ends_clause(punctuation: &str) -> bool {
+    punctuation.contains(['.', '!', '?', ':', '-', '–', '—'])
+}
+
+fn is_small_word(word: &str) -> bool {
+    SMALL_WORDS.iter().any(|w| w.eq_ignore_ascii_case(word))
+}
+
+fn is_pronoun_i(word: &str) -> bool {
+    word == "i" || word.starts_with("i'") || word.starts_with("i’")
+}
+
+/// Returns if a word looks like an acronym that should keep its capitals.
+fn is_acronym(word: &str) -> bool {
+    let letters = word.chars().filter(|c| c.is_alphabetic());
+    if word.chars().count() < 2 || letters.clone().any(char::is_lowercase) {
+        return false;
+    }
+
+    // Accented capitals are counted as vowels, so they're lowercased rather than kept.
+    let has_vowels = letters
+        .clone()
+        .any(|c| !c.is_ascii() || "AEIOUY".contains(c));
+    let is_dotted = word.contains('.');
+    let has_digits = word.chars().any(|c| c.is_ascii_digit()) && letters.clone().count() > 0;
+    !has_vowels || is_dotted || has_digits || is_roman_numeral(word)
+}
+
+/// Returns if a word has lowercase letters and a capital after its first letter, like "iTunes",
+/// "DJs" or "McCartney".
+fn has_mixed_capitals(word: &str) -> bool {
+    word.chars().any(char::is_lowercase) && word.chars().skip(1).any(char::is_uppercase)
+}
+
+/// Returns if a word is a Roman numeral from II to XXXIX.
+fn is_roman_numeral(word: &str) -> bool {
+    let rest = word.trim_start_matches('X');
+    if word.len() - rest.len() > 3 {
+        return false;
+    }
+    matches!(
+        rest,
+        "" | "I" | "II" | "III" | "IV" | "V" | "VI" | "VII" | "VIII" | "IX"
+    ) && word.len() > 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title(s: &str) -> String {
+        Normalizer::new(CaseStyle::Title).apply(s)
+    }
+
+    fn sentence(s: &str) -> String {
+        Normalizer::new(CaseStyle::Sentence).apply(s)
+    }
+
+    #[test]
+    fn small_words_are_capitalized_at_the_ends() {
+        assert_eq!(
+            "The Man Who Sold the World",
+            title("THE MAN WHO SOLD THE WORLD")
+        );
+        assert_eq!(
+            "What Are You Waiting For",
+            title("what are you waiting for")
+        );
+        assert_eq!(
+            "Dreams of the Past: The Return",
+            title("dreams of the past: the return")
+        );
+    }
+
+    #[test]
+    fn parentheses_start_a_phrase() {
+        assert_eq!("Song (The Remix)", title("song (the remix)"));
+        assert_eq!("Song (Remix) of Mine", title("song (remix) of mine"));
+    }
+
+    #[test]
+    fn hyphenated_words_are_capitalized() {
+        assert_eq!("Self-Titled Track", title("self-titled track"));
+        assert_eq!("Self-titled track", sentence("Self-Titled Track"));
+    }
+
+    #[test]
+    fn acronyms_are_preserved() {
+        assert_eq!(
+            "Songs About DJs and R.E.M.",
+            title("songs about DJs and R.E.M.")
+        );
+        assert_eq!("Rocky II theme", sentence("ROCKY II THEME"));
+        assert_eq!("My MP3 Player", title("my MP3 player"));
+        assert_eq!("MGMT", title("MGMT"));
+    }
+
+    #[test]
+    fn given_acronyms_are_used() {
+        let normalizer =
+            Normalizer::new(CaseStyle::Title).with_acronyms(vec![String::from("ABBA")]);
+        assert_eq!("Abba Gold", title("ABBA gold"));
+        assert_eq!("ABBA Gold", normalizer.apply("abba gold"));
+    }
+
+    #[test]
+    fn inner_capitals_are_preserved() {
+        assert_eq!(
+            "The iTunes Song by McCartney",
+            title("the iTunes song by McCartney")
+        );
+    }
+
+    #[test]
+    fn sentence_case_capitalizes_sentences() {
+        assert_eq!(
+            "Hello. Goodbye! I'm here",
+            sentence("HELLO. GOODBYE! I'M HERE")
+        );
+    }
+
+    #[test]
+    fn spacing_is_kept() {
+        assert_eq!("A  Title ", title("a  title "));
+    }
+
+    #[test]
+    fn locked_texts_arent_normalized() {
+        let normalizer = Normalizer::new(CaseStyle::Title);
+        assert_eq!(
+            None,
+            normalizer.normalize(&Text::from("iTUNES").with_lock(true))
+        );
+        assert_eq!(None, normalizer.normalize(&Text::from("Already Done")));
+    }
+
+    #[test]
+    fn ascii_overrides_are_normalized() {
+        let text = Text::from(("本 of music", "hon OF MUSIC")).with_sort(Some("hon"));
+        let normalized = Normalizer::new(CaseStyle::Title).normalize(&text).unwrap();
+        assert_eq!("本 of Music", normalized.value());
+        assert_eq!("Hon of Music", normalized.ascii());
+        assert_eq!(Some("hon"), normalized.sort_override());
+    }
+
+    #[test]
+    fn album_changes_are_listed() {
+        let mut album = raw::Album::new("the END of THE world")
+            .with_artists(vec![Text::from("MGMT")])
+            .with_discs(vec![raw::Disc::from_tracks(vec![
+                raw::Track::new("iTunes").with_artists(vec![Text::from("iTUNES").with_lock(true)]),
+                raw::Track::new("second SONG"),
+            ])]);
+        let changes = Normalizer::new(CaseStyle::Title).normalize_album(&mut album);
+        let changes = changes
+            .iter()
+            .map(|c| (c.location.as_str(), c.new.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("album title", "The End of the World"),
+                ("disc 1 track 2 title", "Second Song"),
+            ],
+            changes
+        );
+        assert_eq!("Second Song", album.discs[0].tracks()[1].title.value());
+    }
+}
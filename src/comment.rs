@@ -0,0 +1,198 @@
+//! Comments on tracks, written to COMM frames.
+
+use crate::{lyrics, Text};
+use serde::{ser, Deserialize, Deserializer, Serialize};
+
+/// A comment, with a description and language to tell it apart from a track's other comments.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Comment {
+    /// The comment itself.
+    pub text: Text,
+
+    /// What the comment is about, like "source", or None for a general comment.
+    pub description: Option<String>,
+
+    /// The ISO 639-2 code of the language the comment is in, or None to use the track's.
+    pub language: Option<String>,
+}
+
+impl Comment {
+    /// Create a general comment, in the track's language.
+    pub fn new<T: Into<Text>>(text: T) -> Self {
+        Self {
+            text: text.into(),
+            description: None,
+            language: None,
+        }
+    }
+
+    pub fn with_description<T: Into<Option<String>>>(mut self, description: T) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn with_language<T: Into<Option<String>>>(mut self, language: T) -> Self {
+        self.language = language.into();
+        self
+    }
+
+    /// Gets the COMM frame the comment is written to, in a default language if it doesn't have
+    /// its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::comment::Comment;
+    /// let comment = Comment::new("24/96 vinyl rip").with_description(String::from("source"));
+    /// let frame = comment.frame("deu");
+    /// assert_eq!(("deu", "source"), (frame.lang.as_str(), frame.description.as_str()));
+    /// ```
+    pub fn frame(&self, default_language: &str) -> id3::frame::Comment {
+        id3::frame::Comment {
+            lang: self
+                .language
+                .as_deref()
+                .unwrap_or(default_language)
+                .to_string(),
+            description: self.description.clone().unwrap_or_default(),
+            text: self.text.value().to_string(),
+        }
+    }
+}
+
+/// Returns if no two comments share a description and language, since only one of them could
+/// be written.
+///
+/// An empty description is the same as none. Comments without a language can only be checked
+/// against each other here, since the track's language isn't known until it's resolved.
+pub(crate) fn are_distinct(comments: &[Comment]) -> bool {
+    let key = |c: &Comment| {
+        (
+            c.description.clone().unwrap_or_default(),
+            c.language.clone(),
+        )
+    };
+    comments
+        .iter()
+        .enumerate()
+        .all(|(i, a)| comments[i + 1..].iter().all(|b| key(a) != key(b)))
+}
+
+impl Serialize for Comment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use crate::utils::ser_opt;
+        use ser::SerializeStruct;
+
+        if self.description.is_none() && self.language.is_none() {
+            return self.text.serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_struct("Comment", 3)?;
+        state.serialize_field("text", &self.text)?;
+        ser_opt(&mut state, self.description.as_deref(), "description")?;
+        ser_opt(&mut state, self.language.as_deref(), "language")?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Comment {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Described {
+            text: Text,
+            #[serde(default)]
+            description: Option<String>,
+            #[serde(default, deserialize_with = "de_language")]
+            language: Option<String>,
+        }
+
+        /// A comment with a description or language, or just its text.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum CommentDef {
+            Described(Described),
+            Text(Text),
+        }
+
+        Ok(match CommentDef::deserialize(deserializer)? {
+            CommentDef::Described(c) => Comment {
+                text: c.text,
+                description: c.description,
+                language: c.language,
+            },
+            CommentDef::Text(text) => Comment::new(text),
+        })
+    }
+}
+
+fn de_language<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    lyrics::check_language(String::deserialize(deserializer)?).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comments_are_parsed() {
+        let comments: Vec<Comment> = serde_yaml::from_str(
+            "
+            - Plain
+            - text: {text: ネット, ascii: Net}
+              description: source
+            - {text: Bonjour, language: fra}
+            - {text: Mixed, ascii: Mixed up}
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                Comment::new("Plain"),
+                Comment::new(("ネット", "Net")).with_description(String::from("source")),
+                Comment::new("Bonjour").with_language(String::from("fra")),
+                Comment::new(("Mixed", "Mixed up")),
+            ],
+            comments
+        );
+    }
+
+    #[test]
+    fn comments_round_trip() {
+        let comments = vec![
+            Comment::new("a"),
+            Comment::new("b")
+                .with_description(String::from("c"))
+                .with_language(String::from("jpn")),
+        ];
+        let yaml = serde_yaml::to_string(&comments).unwrap();
+        assert_eq!(
+            comments,
+            serde_yaml::from_str::<Vec<Comment>>(&yaml).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_language_doesnt_parse() {
+        assert!(serde_yaml::from_str::<Comment>("{text: a, language: fr}").is_err());
+    }
+
+    #[test]
+    fn comments_must_be_distinct() {
+        let a = Comment::new("a");
+        let b = Comment::new("b").with_description(String::from("x"));
+        assert!(are_distinct(&[a.clone(), b.clone()]));
+        assert!(!are_distinct(&[a.clone(), b.clone(), b]));
+        let empty = Comment::new("c").with_description(String::new());
+        assert!(!are_distinct(&[a, empty]));
+    }
+}
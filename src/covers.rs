@@ -0,0 +1,513 @@
+//! Finding where covers come from, and extracting artwork from tagged files.
+
+use crate::{album::Album, artwork::ArtworkKind, image as img, utils::make_file_safe};
+use id3::{
+    frame::{Picture, PictureType},
+    Tag,
+};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    fmt, fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Where a track's or disc's cover comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CoverSource {
+    /// The track's own cover.
+    Track(PathBuf),
+
+    /// The cover of the disc.
+    Disc(PathBuf),
+
+    /// The front cover of the album.
+    Album(PathBuf),
+}
+
+impl CoverSource {
+    /// Gets the path of the source image.
+    pub fn path(&self) -> &Path {
+        match self {
+            CoverSource::Track(path) | CoverSource::Disc(path) | CoverSource::Album(path) => path,
+        }
+    }
+
+    fn level(&self) -> &'static str {
+        match self {
+            CoverSource::Track(_) => "track",
+            CoverSource::Disc(_) => "disc",
+            CoverSource::Album(_) => "album",
+        }
+    }
+}
+
+impl fmt::Display for CoverSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = self.path();
+        let name = path.file_name().map(|n| n.to_string_lossy());
+        write!(
+            f,
+            "{} ({})",
+            name.unwrap_or_else(|| path.to_string_lossy()),
+            self.level()
+        )
+    }
+}
+
+/// An image extracted from the tags of an album's tracks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtractedImage {
+    /// The name the image should be saved under, without an extension.
+    pub name: String,
+
+    /// The file the image should be saved as, relative to the images folder, if the album
+    /// definition names one.
+    pub file: Option<String>,
+
+    /// The mime type of the image.
+    pub mime_type: String,
+
+    /// The encoded image.
+    pub data: Vec<u8>,
+}
+
+impl ExtractedImage {
+    /// Gets the file extension matching the image's mime type.
+    pub fn extension(&self) -> &'static str {
+        if self.mime_type.to_lowercase().contains("png") {
+            "png"
+        } else {
+            "jpg"
+        }
+    }
+
+    /// Gets the file name the image should be saved under.
+    pub fn file_name(&self) -> String {
+        match &self.file {
+            Some(file) => file.clone(),
+            None => format!("{}.{}", self.name, self.extension()),
+        }
+    }
+
+    /// Finds an existing image in a folder that the covers lookups would use instead.
+    pub fn existing<P: AsRef<Path>>(&self, folder: P) -> Option<PathBuf> {
+        match &self.file {
+            Some(file) => Some(folder.as_ref().join(file)).filter(|p| p.exists()),
+            None => img::find_with_name(folder.as_ref(), &self.name),
+        }
+    }
+
+    /// Writes the image into a folder, returning the path it was written to.
+    pub fn write_to<P: AsRef<Path>>(&self, folder: P) -> io::Result<PathBuf> {
+        let path = folder.as_ref().join(self.file_name());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &self.data)?;
+        Ok(path)
+    }
+}
+
+/// An error when extracting artwork.
+#[derive(Debug, Error)]
+pub enum ExtractError {
+    #[error("couldn't read tag of {0:?}: {1}")]
+    CouldntReadTag(PathBuf, id3::Error),
+}
+
+/// The pictures in one track's tag.
+struct TrackPictures {
+    disc_number: usize,
+    /// The name the track's cover is looked up under.
+    name: String,
+    /// The cover the album definition names for the track, if any.
+    cover: Option<String>,
+    pictures: Vec<Picture>,
+}
+
+impl TrackPictures {
+    fn front_cover(&self) -> Option<&Picture> {
+        self.pictures
+            .iter()
+            .find(|p| p.picture_type == PictureType::CoverFront)
+    }
+}
+
+fn hash_of(picture: &Picture) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    picture.data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Collects images, skipping duplicates and avoiding name clashes.
+#[derive(Default)]
+struct Extractor {
+    images: Vec<ExtractedImage>,
+    hashes: HashSet<u64>,
+    names: HashSet<String>,
+    booklet_pages: usize,
+}
+
+impl Extractor {
+    fn contains(&self, picture: &Picture) -> bool {
+        self.hashes.contains(&hash_of(picture))
+    }
+
+    fn add(&mut self, name: String, picture: &Picture) {
+        if !self.hashes.insert(hash_of(picture)) {
+            return;
+        }
+
+        let mut unique = name.clone();
+        let mut n = 2;
+        while !self.names.insert(unique.clone()) {
+            unique = format!("{} ({})", name, n);
+            n += 1;
+        }
+
+        self.images.push(ExtractedImage {
+            name: unique,
+            file: None,
+            mime_type: picture.mime_type.clone(),
+            data: picture.data.clone(),
+        });
+    }
+
+    /// Adds a track's cover under the name its lookup uses, even if another track has the same
+    /// cover, since each track only finds its own.
+    fn add_track_cover(&mut self, track: &TrackPictures, picture: &Picture) {
+        let key = track.cover.as_ref().unwrap_or(&track.name);
+        if !self.names.insert(key.clone()) {
+            return;
+        }
+        self.hashes.insert(hash_of(picture));
+
+        self.images.push(ExtractedImage {
+            name: track.name.clone(),
+            file: track.cover.clone(),
+            mime_type: picture.mime_type.clone(),
+            data: picture.data.clone(),
+        });
+    }
+
+    /// Gets the name that artwork other than a front cover is looked up under.
+    fn artwork_name(&mut self, picture: &Picture, disc_number: usize, multi_disc: bool) -> String {
+        let kind = ArtworkKind::from_picture_type(picture.picture_type);
+
+        // Keep names that the lookups already understand, like ones we embedded ourselves.
+        if kind.is_some() && ArtworkKind::classify(&picture.description).map(|(k, _)| k) == kind {
+            return picture.description.clone();
+        }
+
+        match kind {
+            Some(ArtworkKind::BackCover) => String::from("Back Cover"),
+            Some(ArtworkKind::Media) if multi_disc => format!("Disc {} Media", disc_number),
+            Some(ArtworkKind::Media) => String::from("Media"),
+            Some(ArtworkKind::Booklet) => {
+                self.booklet_pages += 1;
+                format!("Booklet {:02}", self.booklet_pages)
+            }
+            Some(ArtworkKind::Artist) => String::from("Artist"),
+            Some(ArtworkKind::FrontCover) | None => {
+                let name = if picture.description.is_empty() {
+                    picture.picture_type.to_string()
+                } else {
+                    picture.description.clone()
+                };
+                make_file_safe(&name).unwrap_or(name)
+            }
+        }
+    }
+}
+
+/// Returns the most common value, preferring the first seen when tied.
+fn most_common<I: IntoIterator<Item = u64>>(values: I) -> Option<u64> {
+    let mut counts: Vec<(u64, usize)> = Vec::new();
+    for value in values {
+        match counts.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+
+    let max = counts.iter().map(|(_, c)| *c).max()?;
+    counts.into_iter().find(|(_, c)| *c == max).map(|(v, _)| v)
+}
+
+/// Extracts the pictures embedded in an album's tracks, deduplicated by content.
+///
+/// Images are named so the cover lookups find them. The front cover used by most tracks becomes
+/// "Front Cover", one shared by a whole disc is named after the disc, and any other front cover
+/// is saved for each track that has it, as the track's `cover` or named after its title. Other
+/// artwork gets the names in [`ArtworkKind`].
+pub fn extract_artwork(album: &Album) -> Result<Vec<ExtractedImage>, ExtractError> {
+    let mut tracks = Vec::new();
+    for track in album.tracks() {
+        let path = track.path();
+        if !path.exists() {
+            continue;
+        }
+
+        let tag = match Tag::read_from_path(&path) {
+            Ok(tag) => tag,
+            Err(id3::Error {
+                kind: id3::ErrorKind::NoTag,
+                ..
+            }) => continue,
+            Err(e) => return Err(ExtractError::CouldntReadTag(path, e)),
+        };
+
+        let cover = track.raw().cover().map(str::to_string);
+        let name = match &cover {
+            Some(file) => img::file_stem(file).into_owned(),
+            None => track.title().file_safe().to_string(),
+        };
+        tracks.push(TrackPictures {
+            disc_number: track.disc().disc_number,
+            name,
+            cover,
+            pictures: tag.pictures().cloned().collect(),
+        });
+    }
+
+    let mut extractor = Extractor::default();
+    let multi_disc = album.num_discs() > 1;
+
+    let album_front = most_common(tracks.iter().filter_map(|t| t.front_cover().map(hash_of)));
+    if let Some(picture) = tracks
+        .iter()
+        .filter_map(TrackPictures::front_cover)
+        .find(|p| Some(hash_of(p)) == album_front)
+    {
+        extractor.add(String::from("Front Cover"), picture);
+    }
+
+    // A disc gets its own cover when all of its tracks share one that isn't the album's.
+    let mut disc_fronts = HashMap::new();
+    if multi_disc {
+        for disc in album.discs() {
+            let on_disc: Vec<_> = tracks
+                .iter()
+                .filter(|t| t.disc_number == disc.disc_number)
+                .collect();
+            let fronts: Option<Vec<_>> = on_disc.iter().map(|t| t.front_cover()).collect();
+            let fronts = match fronts {
+                Some(fronts) if !fronts.is_empty() => fronts,
+                _ => continue,
+            };
+
+            let hash = hash_of(fronts[0]);
+            if Some(hash) != album_front && fronts.iter().all(|p| hash_of(p) == hash) {
+                if let Some(name) = disc.filename() {
+                    extractor.add(name, fronts[0]);
+                }
+                disc_fronts.insert(disc.disc_number, hash);
+            }
+        }
+    }
+
+    for track in &tracks {
+        if let Some(picture) = track.front_cover() {
+            let hash = hash_of(picture);
+            if Some(hash) != album_front && disc_fronts.get(&track.disc_number) != Some(&hash) {
+                extractor.add_track_cover(track, picture);
+            }
+        }
+    }
+
+    for track in &tracks {
+        for picture in &track.pictures {
+            if picture.picture_type == PictureType::CoverFront || extractor.contains(picture) {
+                continue;
+            }
+            let name = extractor.artwork_name(picture, track.disc_number, multi_disc);
+            extractor.add(name, picture);
+        }
+    }
+
+    Ok(extractor.images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw;
+    use id3::Version;
+
+    fn picture(picture_type: PictureType, description: &str, data: u8) -> Picture {
+        Picture {
+            mime_type: String::from("image/png"),
+            picture_type,
+            description: String::from(description),
+            data: vec![data],
+        }
+    }
+
+    fn tag_tracks(album: &Album, pictures: &[Vec<Picture>]) {
+        for (track, pictures) in album.tracks().zip(pictures) {
+            let path = track.path();
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, []).unwrap();
+
+            let mut tag = Tag::new();
+            tag.extend(pictures.iter().cloned().map(id3::Frame::from));
+            tag.write_to_path(&path, Version::Id3v24).unwrap();
+        }
+    }
+
+    fn names(images: &[ExtractedImage]) -> Vec<&str> {
+        images.iter().map(|i| i.name.as_str()).collect()
+    }
+
+    #[test]
+    fn shared_covers_are_extracted_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = raw::Album::new("foo").with_discs(vec![raw::Disc::from_tracks(vec![
+            raw::Track::new("one"),
+            raw::Track::new("two"),
+            raw::Track::new("three"),
+        ])]);
+        let album = Album::new(album, dir.path());
+        tag_tracks(
+            &album,
+            &[
+                vec![
+                    picture(PictureType::CoverFront, "", 1),
+                    picture(PictureType::CoverBack, "", 2),
+                ],
+                vec![
+                    picture(PictureType::CoverFront, "", 1),
+                    picture(PictureType::Leaflet, "", 3),
+                ],
+                vec![picture(PictureType::CoverFront, "", 4)],
+            ],
+        );
+
+        let images = extract_artwork(&album).unwrap();
+        assert_eq!(
+            vec!["Front Cover", "three", "Back Cover", "Booklet 01"],
+            names(&images)
+        );
+        assert_eq!("png", images[0].extension());
+    }
+
+    #[test]
+    fn tracks_sharing_a_cover_each_get_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = raw::Album::new("foo").with_discs(vec![raw::Disc::from_tracks(vec![
+            raw::Track::new("a"),
+            raw::Track::new("b"),
+            raw::Track::new("c"),
+            raw::Track::new("d"),
+            raw::Track::new("e").with_cover(String::from("art/e.png")),
+        ])]);
+        let album = Album::new(album, dir.path());
+        tag_tracks(
+            &album,
+            &[
+                vec![picture(PictureType::CoverFront, "", 1)],
+                vec![picture(PictureType::CoverFront, "", 1)],
+                vec![picture(PictureType::CoverFront, "", 2)],
+                vec![picture(PictureType::CoverFront, "", 2)],
+                vec![picture(PictureType::CoverFront, "", 3)],
+            ],
+        );
+
+        let images = extract_artwork(&album).unwrap();
+        assert_eq!(vec!["Front Cover", "c", "d", "e"], names(&images));
+        assert_eq!("art/e.png", images[3].file_name());
+
+        let folder = dir.path().join("extras/images");
+        for image in &images {
+            image.write_to(&folder).unwrap();
+        }
+        let sources = album
+            .tracks()
+            .map(|t| t.cover_source().unwrap())
+            .collect::<Vec<_>>();
+        assert!(matches!(sources[1], CoverSource::Album(_)));
+        for source in &sources[2..] {
+            assert!(
+                matches!(source, CoverSource::Track(p) if p.exists()),
+                "{:?}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn disc_covers_are_named_after_the_disc() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = raw::Album::new("foo").with_discs(vec![
+            raw::Disc::from_tracks(vec![raw::Track::new("a"), raw::Track::new("b")]),
+            raw::Disc::from_tracks(vec![raw::Track::new("c"), raw::Track::new("d")]),
+            raw::Disc::from_tracks(vec![raw::Track::new("e")]),
+        ]);
+        let album = Album::new(album, dir.path());
+        tag_tracks(
+            &album,
+            &[
+                vec![picture(PictureType::CoverFront, "", 1)],
+                vec![picture(PictureType::CoverFront, "", 1)],
+                vec![
+                    picture(PictureType::CoverFront, "", 2),
+                    picture(PictureType::Media, "", 3),
+                ],
+                vec![picture(PictureType::CoverFront, "", 2)],
+                vec![picture(PictureType::CoverFront, "", 1)],
+            ],
+        );
+
+        let images = extract_artwork(&album).unwrap();
+        assert_eq!(
+            vec!["Front Cover", "Disc 2", "Disc 2 Media"],
+            names(&images)
+        );
+    }
+
+    #[test]
+    fn recognised_descriptions_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = raw::Album::new("foo")
+            .with_discs(vec![raw::Disc::from_tracks(vec![raw::Track::new("a")])]);
+        let album = Album::new(album, dir.path());
+        tag_tracks(
+            &album,
+            &[vec![
+                picture(PictureType::Leaflet, "Booklet 07", 1),
+                picture(PictureType::Other, "scan: front", 2),
+            ]],
+        );
+
+        let images = extract_artwork(&album).unwrap();
+        assert_eq!(vec!["Booklet 07", "scan - front"], names(&images));
+    }
+
+    #[test]
+    fn cover_sources_fall_back_to_the_album() {
+        let dir = tempfile::tempdir().unwrap();
+        let images = dir.path().join("extras/images");
+        fs::create_dir_all(&images).unwrap();
+        for name in ["Front Cover.png", "Disc 2.jpg", "b.png"] {
+            fs::write(images.join(name), []).unwrap();
+        }
+
+        let album = raw::Album::new("foo").with_discs(vec![
+            raw::Disc::from_tracks(vec![raw::Track::new("a")]),
+            raw::Disc::from_tracks(vec![raw::Track::new("b"), raw::Track::new("c")]),
+        ]);
+        let album = Album::new(album, dir.path());
+        let sources: Vec<_> = album.tracks().map(|t| t.cover_source()).collect();
+        assert_eq!(
+            vec![
+                Some(CoverSource::Album(images.join("Front Cover.png"))),
+                Some(CoverSource::Track(images.join("b.png"))),
+                Some(CoverSource::Disc(images.join("Disc 2.jpg"))),
+            ],
+            sources
+        );
+    }
+}
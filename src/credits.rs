@@ -0,0 +1,271 @@
+//! People credited for a track besides its artists, like composers and producers.
+
+use crate::{artists::ArtistStyle, Text};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+
+/// A list of people by the role they played, like "producer" or "piano".
+pub type Roles = BTreeMap<String, Vec<Text>>;
+
+/// The people credited for an album, disc or track.
+///
+/// Every field is inherited separately, so a track that only sets its composers still has its
+/// album's conductors.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Credits {
+    /// The composers, written to TCOM.
+    #[serde(alias = "composer", deserialize_with = "de_people")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub composers: Option<Vec<Text>>,
+
+    /// The lyricists, written to TEXT.
+    #[serde(alias = "lyricist", deserialize_with = "de_people")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lyricists: Option<Vec<Text>>,
+
+    /// The conductors, written to TPE3.
+    #[serde(alias = "conductor", deserialize_with = "de_people")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conductors: Option<Vec<Text>>,
+
+    /// The remixers, written to TPE4.
+    #[serde(alias = "remixer", deserialize_with = "de_people")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remixers: Option<Vec<Text>>,
+
+    /// People involved in the recording by role, like producers and engineers, written to TIPL.
+    #[serde(deserialize_with = "de_roles")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub involved: Option<Roles>,
+
+    /// Musicians by instrument, written to TMCL.
+    #[serde(deserialize_with = "de_roles")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub musicians: Option<Roles>,
+}
+
+impl Credits {
+    /// Returns if no one is credited.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Fills in the fields that aren't set from a disc's or album's credits.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::{credits::Credits, Text};
+    /// let track = Credits {
+    ///     composers: Some(vec![Text::from("Schubert")]),
+    ///     ..Default::default()
+    /// };
+    /// let album = Credits {
+    ///     composers: Some(vec![Text::from("Beethoven")]),
+    ///     conductors: Some(vec![Text::from("Karajan")]),
+    ///     ..Default::default()
+    /// };
+    /// let credits = track.inherit(&album);
+    /// assert_eq!(Some(&[Text::from("Schubert")][..]), credits.composers.as_deref());
+    /// assert_eq!(Some(&[Text::from("Karajan")][..]), credits.conductors.as_deref());
+    /// ```
+    pub fn inherit(&self, parent: &Credits) -> Credits {
+        fn or<T: Clone>(own: &Option<T>, parent: &Option<T>) -> Option<T> {
+            own.as_ref().or(parent.as_ref()).cloned()
+        }
+
+        Credits {
+            composers: or(&self.composers, &parent.composers),
+            lyricists: or(&self.lyricists, &parent.lyricists),
+            conductors: or(&self.conductors, &parent.conductors),
+            remixers: or(&self.remixers, &parent.remixers),
+            involved: or(&self.involved, &parent.involved),
+            musicians: or(&self.musicians, &parent.musicians),
+        }
+    }
+
+    /// Gets every person credited, with the field they're in.
+    pub fn texts(&self) -> Vec<(&'static str, &Text)> {
+        let mut texts = Vec::new();
+        for (name, people) in [
+            ("composer", &self.composers),
+            ("lyricist", &self.lyricists),
+            ("conductor", &self.conductors),
+            ("remixer", &self.remixers),
+        ] {
+            texts.extend(people.iter().flatten().map(|p| (name, p)));
+        }
+        for (name, roles) in [("involved", &self.involved), ("musician", &self.musicians)] {
+            texts.extend(
+                roles
+                    .iter()
+                    .flat_map(Roles::values)
+                    .flatten()
+                    .map(|p| (name, p)),
+            );
+        }
+        texts
+    }
+
+    /// Gets every person credited to change them, with the field they're in.
+    pub fn texts_mut(&mut self) -> Vec<(&'static str, &mut Text)> {
+        let mut texts = Vec::new();
+        for (name, people) in [
+            ("composer", &mut self.composers),
+            ("lyricist", &mut self.lyricists),
+            ("conductor", &mut self.conductors),
+            ("remixer", &mut self.remixers),
+        ] {
+            texts.extend(people.iter_mut().flatten().map(|p| (name, p)));
+        }
+        for (name, roles) in [
+            ("involved", &mut self.involved),
+            ("musician", &mut self.musicians),
+        ] {
+            texts.extend(
+                roles
+                    .iter_mut()
+                    .flat_map(Roles::values_mut)
+                    .flatten()
+                    .map(|p| (name, p)),
+            );
+        }
+        texts
+    }
+
+    /// Gets the frame ID, name and contents of the frames the credits are written to.
+    ///
+    /// Lists of people are written like artists. Roles are written as ID3v2.4 pairs of a role
+    /// and a person, once for each person.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::{artists::ArtistStyle, credits::Credits, Text};
+    /// let credits: Credits = serde_yaml::from_str("involved: {producer: [A, B]}").unwrap();
+    /// let [.., (id, _, involved), _] = credits.frames(ArtistStyle::default());
+    /// assert_eq!("TIPL", id);
+    /// assert_eq!(Some("producer\0A\0producer\0B"), involved.as_deref());
+    /// ```
+    pub fn frames(&self, style: ArtistStyle) -> [(&'static str, &'static str, Option<String>); 6] {
+        let people = |people: &Option<Vec<Text>>| {
+            people
+                .as_deref()
+                .filter(|p| !p.is_empty())
+                .map(|p| style.frame_value(p))
+        };
+        let roles = |roles: &Option<Roles>| {
+            let pairs = roles
+                .iter()
+                .flatten()
+                .flat_map(|(role, people)| {
+                    people
+                        .iter()
+                        .flat_map(move |person| [role.as_str(), person.value()])
+                })
+                .collect::<Vec<_>>();
+            (!pairs.is_empty()).then(|| pairs.join("\0"))
+        };
+
+        [
+            ("TCOM", "composer", people(&self.composers)),
+            ("TEXT", "lyricist", people(&self.lyricists)),
+            ("TPE3", "conductor", people(&self.conductors)),
+            ("TPE4", "remixer", people(&self.remixers)),
+            ("TIPL", "involved people", roles(&self.involved)),
+            ("TMCL", "musician credits", roles(&self.musicians)),
+        ]
+    }
+}
+
+/// One person, or a list of them.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMore {
+    One(Text),
+    More(Vec<Text>),
+}
+
+impl From<OneOrMore> for Vec<Text> {
+    fn from(people: OneOrMore) -> Self {
+        match people {
+            OneOrMore::One(person) => vec![person],
+            OneOrMore::More(people) => people,
+        }
+    }
+}
+
+fn de_people<'de, D>(deserializer: D) -> Result<Option<Vec<Text>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    OneOrMore::deserialize(deserializer).map(|people| Some(people.into()))
+}
+
+fn de_roles<'de, D>(deserializer: D) -> Result<Option<Roles>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let roles = BTreeMap::<String, OneOrMore>::deserialize(deserializer)?;
+    Ok(Some(
+        roles
+            .into_iter()
+            .map(|(role, people)| (role, people.into()))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credits_are_parsed() {
+        let credits: Credits = serde_yaml::from_str(
+            "
+            composer: Ludwig van Beethoven
+            lyricists: [Friedrich Schiller]
+            conductor: Herbert von Karajan
+            involved:
+                producer: [Otto Gerdes, Hans Weber]
+            musicians:
+                soprano: Gundula Janowitz
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            Some(vec![Text::from("Ludwig van Beethoven")]),
+            credits.composers
+        );
+        assert_eq!(
+            Some(&vec![Text::from("Gundula Janowitz")]),
+            credits.musicians.as_ref().and_then(|m| m.get("soprano"))
+        );
+        assert_eq!(2, credits.involved.unwrap()["producer"].len());
+    }
+
+    #[test]
+    fn credits_round_trip() {
+        let credits: Credits =
+            serde_yaml::from_str("{composer: A, musicians: {piano: [B, C]}}").unwrap();
+        let yaml = serde_yaml::to_string(&credits).unwrap();
+        assert_eq!(credits, serde_yaml::from_str(&yaml).unwrap());
+    }
+
+    #[test]
+    fn empty_list_overrides_parent() {
+        let track: Credits = serde_yaml::from_str("remixers: []").unwrap();
+        let album: Credits = serde_yaml::from_str("remixer: A").unwrap();
+        let credits = track.inherit(&album);
+        assert_eq!(Some(Vec::new()), credits.remixers);
+        assert_eq!(None, credits.frames(ArtistStyle::default())[3].2);
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        let credits = serde_yaml::from_str::<Credits>("{producer: A, composer: B}").unwrap();
+        assert_eq!(Some(vec![Text::from("B")]), credits.composers);
+        assert_eq!(None, credits.involved);
+    }
+}
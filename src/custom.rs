@@ -0,0 +1,200 @@
+//! Custom fields from the album definition, written to user text and link frames.
+
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+
+/// Custom fields on an album, disc or track, like where it was bought.
+///
+/// Fields are inherited one by one, and a field set to `~` removes the one it would inherit.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Custom {
+    fields: BTreeMap<String, Option<String>>,
+}
+
+/// The frame a custom field is written to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CustomFrame<'a> {
+    /// A TXXX user text frame.
+    Text(&'a str),
+    /// A WXXX user link frame, for fields that are web addresses.
+    Link(&'a str),
+}
+
+impl Custom {
+    /// Returns if no fields are set or removed.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Gets the value of a field, if it's set.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.get(name)?.as_deref()
+    }
+
+    /// Sets a field, or removes it from what's inherited if the value is None.
+    pub fn with<T: Into<Option<String>>>(mut self, name: &str, value: T) -> Self {
+        self.fields.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Adds the fields of a disc or album that aren't set or removed here.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::custom::Custom;
+    /// let album = Custom::default()
+    ///     .with("source", String::from("CD"))
+    ///     .with("shop", String::from("Rough Trade"));
+    /// let track = Custom::default()
+    ///     .with("source", String::from("vinyl rip"))
+    ///     .with("shop", None);
+    /// let custom = track.inherit(&album);
+    /// assert_eq!(Some("vinyl rip"), custom.get("source"));
+    /// assert_eq!(None, custom.get("shop"));
+    /// ```
+    pub fn inherit(&self, parent: &Custom) -> Custom {
+        let mut fields = parent.fields.clone();
+        fields.extend(self.fields.clone());
+        Custom { fields }
+    }
+
+    /// Gets the fields that are set, with the frame each is written to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::custom::{Custom, CustomFrame};
+    /// let custom: Custom =
+    ///     serde_yaml::from_str("{shop: 'https://example.com/album', id: '42'}").unwrap();
+    /// let frames = custom.frames().collect::<Vec<_>>();
+    /// assert_eq!(("id", CustomFrame::Text("42")), frames[0]);
+    /// assert_eq!(("shop", CustomFrame::Link("https://example.com/album")), frames[1]);
+    /// ```
+    pub fn frames(&self) -> impl Iterator<Item = (&str, CustomFrame<'_>)> {
+        self.fields.iter().filter_map(|(name, value)| {
+            let value = value.as_deref()?;
+            let frame = if is_url(value) {
+                CustomFrame::Link(value)
+            } else {
+                CustomFrame::Text(value)
+            };
+            Some((name.as_str(), frame))
+        })
+    }
+}
+
+/// Returns if a value is a web address, and is written to a link frame.
+pub fn is_url(value: &str) -> bool {
+    let lowercase = value.to_ascii_lowercase();
+    (lowercase.starts_with("http://") || lowercase.starts_with("https://"))
+        && !value.contains(char::is_whitespace)
+}
+
+impl<'de> Deserialize<'de> for Custom {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// A field's value. Numbers are read too, only to ask for quotes: by the time they're
+        /// parsed, "1.10", "0x1F" and "+5" can't be told apart from "1.1", "31" and "5".
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Scalar {
+            String(String),
+            Bool(bool),
+            Number(f64),
+        }
+
+        let fields = BTreeMap::<String, Option<Scalar>>::deserialize(deserializer)?;
+        let mut custom = Custom::default();
+        for (name, value) in fields {
+            if name.is_empty() {
+                return Err(de::Error::custom("custom fields need a name"));
+            }
+            if crate::track::is_reserved_description(&name) {
+                return Err(de::Error::custom(format_args!(
+                    "\"{}\" is written by maestro, and can't be a custom field",
+                    name
+                )));
+            }
+            let value = match value {
+                None => None,
+                Some(Scalar::String(s)) => Some(s),
+                Some(Scalar::Bool(b)) => Some(b.to_string()),
+                Some(Scalar::Number(number)) => {
+                    return Err(de::Error::custom(format_args!(
+                        "custom field \"{}\" is read as the number {}, so it needs quotes to be \
+                         kept as written",
+                        name, number
+                    )))
+                }
+            };
+            custom.fields.insert(name, value);
+        }
+        Ok(custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_fields_are_parsed() {
+        let custom: Custom = serde_yaml::from_str(
+            "
+            source: vinyl rip 24/96
+            id: '1024'
+            version: '1.10'
+            remastered: true
+            shop: https://example.com/albums/1
+            removed: ~
+            ",
+        )
+        .unwrap();
+        assert_eq!(Some("vinyl rip 24/96"), custom.get("source"));
+        assert_eq!(Some("1024"), custom.get("id"));
+        assert_eq!(Some("1.10"), custom.get("version"));
+        assert_eq!(Some("true"), custom.get("remastered"));
+        assert_eq!(None, custom.get("removed"));
+        assert_eq!(5, custom.frames().count());
+
+        for number in ["1024", "1.10", "1e3", "0x1F", "+5"] {
+            let yaml = format!("v: {}", number);
+            assert!(serde_yaml::from_str::<Custom>(&yaml).is_err(), "{}", yaml);
+        }
+    }
+
+    #[test]
+    fn custom_fields_round_trip() {
+        let custom = Custom::default()
+            .with("a", String::from("b"))
+            .with("c", None);
+        let yaml = serde_yaml::to_string(&custom).unwrap();
+        assert_eq!(custom, serde_yaml::from_str(&yaml).unwrap());
+    }
+
+    #[test]
+    fn removed_fields_stay_removed() {
+        let album = Custom::default().with("a", String::from("b"));
+        let disc = Custom::default().with("a", None);
+        let track = Custom::default().inherit(&disc.inherit(&album));
+        assert_eq!(0, track.frames().count());
+    }
+
+    #[test]
+    fn reserved_fields_dont_parse() {
+        assert!(serde_yaml::from_str::<Custom>("barcode: x").is_err());
+        assert!(serde_yaml::from_str::<Custom>("Alternate Title: x").is_err());
+        assert!(serde_yaml::from_str::<Custom>("'': x").is_err());
+    }
+
+    #[test]
+    fn urls_need_a_scheme() {
+        assert!(is_url("HTTPS://example.com"));
+        assert!(!is_url("example.com"));
+        assert!(!is_url("https://example.com and more"));
+    }
+}
@@ -0,0 +1,256 @@
+//! Release dates with year, month or day precision.
+
+use serde::{de, ser, Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// A date that's only as precise as what's known, like "1997", "1997-05" or "1997-05-21".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: Option<u8>,
+    day: Option<u8>,
+}
+
+impl Date {
+    /// Create a date that's only known to the year.
+    pub fn from_year(year: u16) -> Self {
+        Self {
+            year,
+            month: None,
+            day: None,
+        }
+    }
+
+    /// Create a date, checking that the month and day exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::date::Date;
+    /// assert!(Date::new(2000, Some(2), Some(29)).is_some());
+    /// assert!(Date::new(1999, Some(2), Some(29)).is_none());
+    /// assert!(Date::new(1999, None, Some(1)).is_none());
+    /// ```
+    pub fn new(year: u16, month: Option<u8>, day: Option<u8>) -> Option<Self> {
+        let valid = match (month, day) {
+            (None, None) => true,
+            (Some(month), None) => (1..=12).contains(&month),
+            (Some(month), Some(day)) => {
+                (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)
+            }
+            (None, Some(_)) => false,
+        };
+        valid.then_some(Self { year, month, day })
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> Option<u8> {
+        self.month
+    }
+
+    pub fn day(&self) -> Option<u8> {
+        self.day
+    }
+
+    /// Returns if only the year is known.
+    pub fn is_year_only(&self) -> bool {
+        self.month.is_none()
+    }
+
+    /// Gets the key the date is written with in manifests, keeping "year" for bare years.
+    pub(crate) fn key(&self) -> &'static str {
+        if self.is_year_only() {
+            "year"
+        } else {
+            "date"
+        }
+    }
+
+    /// Gets the date as an ID3 timestamp.
+    pub fn to_timestamp(self) -> id3::Timestamp {
+        id3::Timestamp {
+            year: self.year.into(),
+            month: self.month,
+            day: self.day,
+            hour: None,
+            minute: None,
+            second: None,
+        }
+    }
+
+    /// Gets the date from an ID3 timestamp, ignoring the time.
+    pub fn from_timestamp(timestamp: &id3::Timestamp) -> Option<Self> {
+        let year = u16::try_from(timestamp.year).ok()?;
+        Self::new(year, timestamp.month, timestamp.month.and(timestamp.day))
+    }
+}
+
+/// Gets the original release date from a tag's TDOR frame, which `id3` has no accessor for.
+pub fn original_release(tag: &id3::Tag) -> Option<Date> {
+    use id3::TagLike;
+    let timestamp = tag.get("TDOR")?.content().text()?.parse().ok()?;
+    Date::from_timestamp(&timestamp)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    /// Writes the date in ISO 8601 form.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}", self.year)?;
+        if let Some(month) = self.month {
+            write!(f, "-{:02}", month)?;
+        }
+        if let Some(day) = self.day {
+            write!(f, "-{:02}", day)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Parses an ISO 8601 date, like "1997", "1997-05" or "1997-05-21".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::date::Date;
+    /// let date: Date = "1997-05-21".parse().unwrap();
+    /// assert_eq!((1997, Some(5), Some(21)), (date.year(), date.month(), date.day()));
+    /// assert!("1997-13".parse::<Date>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid date \"{}\", expected YYYY, YYYY-MM or YYYY-MM-DD",
+                s
+            )
+        };
+        let mut parts = s.split('-');
+        let mut next = |len: usize| -> Result<Option<u16>, String> {
+            match parts.next() {
+                None => Ok(None),
+                Some(p) if p.len() == len && p.chars().all(|c| c.is_ascii_digit()) => {
+                    p.parse().map(Some).map_err(|_| invalid())
+                }
+                Some(_) => Err(invalid()),
+            }
+        };
+
+        let year = next(4)?.ok_or_else(invalid)?;
+        let month = next(2)?.map(|m| m as u8);
+        let day = next(2)?.map(|d| d as u8);
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Self::new(year, month, day).ok_or_else(invalid)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        if self.is_year_only() {
+            serializer.serialize_u64(self.year.into())
+        } else {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DateVisitor)
+    }
+}
+
+/// Visits a year or an ISO date.
+pub(crate) struct DateVisitor;
+
+impl<'de> de::Visitor<'de> for DateVisitor {
+    type Value = Date;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a year or an ISO date")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        u16::try_from(value)
+            .map(Date::from_year)
+            .map_err(|_| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_dates_are_displayed() {
+        assert_eq!("0999", Date::from_year(999).to_string());
+        assert_eq!(
+            "1997-05",
+            Date::new(1997, Some(5), None).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn malformed_dates_dont_parse() {
+        for s in [
+            "97",
+            "1997-5",
+            "1997-05-21-01",
+            "1997-02-30",
+            "May 1997",
+            "",
+        ] {
+            assert!(s.parse::<Date>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn yaml_round_trips() {
+        for yaml in ["1997", "1997-05", "1997-05-21"] {
+            let date: Date = serde_yaml::from_str(yaml).unwrap();
+            assert_eq!(yaml, serde_yaml::to_string(&date).unwrap().trim());
+        }
+    }
+
+    #[test]
+    fn timestamp_time_is_ignored() {
+        let timestamp: id3::Timestamp = "1997-05-21T10:00".parse().unwrap();
+        assert_eq!(
+            Date::new(1997, Some(5), Some(21)),
+            Date::from_timestamp(&timestamp)
+        );
+    }
+}
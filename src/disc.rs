@@ -1,11 +1,19 @@
 use super::{album::Album, track::Track};
 use crate::{
+    covers::CoverSource,
+    credits::Credits,
+    custom::Custom,
+    date::Date,
     image::{self as img, Image, LoadWithCacheError},
+    profile::ExportFormat,
     raw,
     utils::num_digits,
 };
 use once_cell::sync::OnceCell;
-use std::{borrow::Cow, path::Path};
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
 
 #[derive(Clone)]
 pub struct Disc<'a> {
@@ -31,11 +39,31 @@ impl<'a> Disc<'a> {
         self.disc.num_tracks()
     }
 
+    /// Gets the disc's own release date, if it has one.
+    pub fn date(&self) -> Option<Date> {
+        self.disc.date()
+    }
+
+    /// Gets the disc's own original release date, if it has one.
+    pub fn original_date(&self) -> Option<Date> {
+        self.disc.original_date()
+    }
+
+    /// Gets the disc's credits, with the album's filling in any roles it doesn't set.
+    pub fn credits(&self) -> Credits {
+        self.disc.credits().inherit(self.album.credits())
+    }
+
+    /// Gets the disc's custom fields, with the album's it doesn't set or remove.
+    pub fn custom(&self) -> Custom {
+        self.disc.custom().inherit(self.album.custom())
+    }
+
     pub fn is_only_disc(&self) -> bool {
         self.album.num_discs() == 1
     }
 
-    pub fn track(&self, track_number: usize) -> Option<Track> {
+    pub fn track(&self, track_number: usize) -> Option<Track<'_>> {
         self.disc
             .tracks()
             .get(track_number - 1)
@@ -49,7 +77,7 @@ impl<'a> Disc<'a> {
             .map(|track| Track::new(Cow::Owned(self), track, track_number))
     }
 
-    pub fn tracks(&self) -> impl Iterator<Item = Track> {
+    pub fn tracks(&self) -> impl Iterator<Item = Track<'_>> {
         self.disc
             .tracks()
             .iter()
@@ -74,7 +102,7 @@ impl<'a> Disc<'a> {
         }
     }
 
-    pub fn path(&self) -> Cow<Path> {
+    pub fn path(&self) -> Cow<'_, Path> {
         let album_path = self.album.path();
         match self.filename() {
             None => album_path.into(),
@@ -82,20 +110,33 @@ impl<'a> Disc<'a> {
         }
     }
 
-    fn get_cover<'b, P, F, G>(
+    fn get_cover<'b, G>(
         &'b self,
         cover: &'b OnceCell<Option<Image>>,
-        covers_path: P,
-        transform: F,
+        format: ExportFormat,
         fallback: G,
     ) -> Result<Option<&'b Image>, LoadWithCacheError>
     where
-        P: AsRef<Path>,
-        F: Fn(image::DynamicImage) -> Result<Image, image::ImageError>,
         G: Fn() -> Result<Option<&'b Image>, LoadWithCacheError>,
     {
         cover
             .get_or_try_init(|| {
+                let settings = self.album.profile(format).cover;
+                let covers_path = self.album.covers_path_for(&settings);
+                let transform = |raw| img::transform_cover(raw, &settings);
+
+                if let Some(file) = self.disc.cover() {
+                    let cache_name =
+                        format!("Disc {} - {}", self.disc_number, img::file_stem(file));
+                    return Image::load_file_with_cache(
+                        self.album.image_path().join(file),
+                        covers_path,
+                        &cache_name,
+                        transform,
+                    )
+                    .map(Some);
+                }
+
                 let name = match self.filename() {
                     Some(name) => name,
                     None => return Ok(None),
@@ -109,22 +150,27 @@ impl<'a> Disc<'a> {
             })
     }
 
+    /// Gets the path of the disc's own source cover, without falling back to the album's.
+    pub fn cover_file(&self) -> Option<PathBuf> {
+        match self.disc.cover() {
+            Some(file) => Some(self.album.image_path().join(file)),
+            None => img::find_with_name(&self.album.image_path(), &self.filename()?),
+        }
+    }
+
+    /// Gets where the disc's cover comes from.
+    pub fn cover_source(&self) -> Option<CoverSource> {
+        self.cover_file()
+            .map(CoverSource::Disc)
+            .or_else(|| self.album.cover_file().map(CoverSource::Album))
+    }
+
     pub fn cover(&self) -> Result<Option<&Image>, LoadWithCacheError> {
-        self.get_cover(
-            &self.cover,
-            self.album.covers_path(),
-            img::transform_image,
-            || self.album.cover(),
-        )
+        self.get_cover(&self.cover, ExportFormat::Full, || self.album.cover())
     }
 
     pub fn cover_vw(&self) -> Result<Option<&Image>, LoadWithCacheError> {
-        self.get_cover(
-            &self.cover_vw,
-            self.album.covers_vw_path(),
-            img::transform_image_vw,
-            || self.album.cover_vw(),
-        )
+        self.get_cover(&self.cover_vw, ExportFormat::Vw, || self.album.cover_vw())
     }
 }
 
@@ -0,0 +1,311 @@
+//! Rules for making names safe on different filesystems.
+
+use crate::utils::make_file_safe;
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, fmt, str::FromStr};
+
+/// A set of rules for making names safe to use as filenames.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FileSafety {
+    /// Only replaces slashes, for Linux and macOS filesystems.
+    Posix,
+
+    /// Replaces characters Windows forbids, and avoids trailing dots and reserved names.
+    #[default]
+    Windows,
+
+    /// Windows rules, also replacing characters that FAT short names forbid.
+    Fat32,
+
+    /// Only keeps letters, numbers, spaces and a handful of punctuation.
+    StrictAscii,
+}
+
+/// Names Windows reserves for devices, with or without an extension.
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// The name used when nothing safe is left, or the name would refer to a folder itself.
+const PLACEHOLDER: &str = "_";
+
+/// The longest name allowed, in bytes for POSIX and characters otherwise.
+const MAX_LENGTH: usize = 255;
+
+impl FileSafety {
+    /// Every file safety profile.
+    pub const ALL: [FileSafety; 4] = [
+        FileSafety::Posix,
+        FileSafety::Windows,
+        FileSafety::Fat32,
+        FileSafety::StrictAscii,
+    ];
+
+    /// Gets the profile's name.
+    pub fn name(self) -> &'static str {
+        match self {
+            FileSafety::Posix => "posix",
+            FileSafety::Windows => "windows",
+            FileSafety::Fat32 => "fat32",
+            FileSafety::StrictAscii => "strict-ascii",
+        }
+    }
+
+    /// Replaces the characters the profile doesn't allow, without looking at the name as a
+    /// whole.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::file_safety::FileSafety;
+    /// assert_eq!("AC-DC: Live", FileSafety::Posix.map_chars("AC/DC: Live"));
+    /// assert_eq!("AC-DC - Live", FileSafety::Windows.map_chars("AC/DC: Live"));
+    /// assert_eq!("Tom_Jerry (Live)", FileSafety::StrictAscii.map_chars("Tom+Jerry [Live]"));
+    /// ```
+    pub fn map_chars(self, s: &str) -> Cow<'_, str> {
+        match self {
+            FileSafety::Posix => replace_chars(s.into(), |c| match c {
+                '/' => Some("-"),
+                '\0' => Some(""),
+                _ => None,
+            }),
+            FileSafety::Windows => {
+                let s = make_file_safe(s)
+                    .map(Cow::Owned)
+                    .unwrap_or(Cow::Borrowed(s));
+                replace_chars(s, |c| c.is_control().then_some(""))
+            }
+            FileSafety::Fat32 => replace_chars(FileSafety::Windows.map_chars(s), |c| match c {
+                '[' => Some("("),
+                ']' => Some(")"),
+                '+' | ',' | ';' | '=' => Some("_"),
+                _ => None,
+            }),
+            FileSafety::StrictAscii => replace_chars(FileSafety::Fat32.map_chars(s), |c| match c {
+                c if c.is_ascii_alphanumeric() => None,
+                ' ' | '-' | '_' | '.' | '(' | ')' => None,
+                '\'' | '!' => Some(""),
+                '&' => Some("and"),
+                _ => Some("_"),
+            }),
+        }
+    }
+
+    /// Makes a whole name safe, such as a folder name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::file_safety::FileSafety;
+    /// assert_eq!("Hello...", FileSafety::Posix.make_safe("Hello..."));
+    /// assert_eq!("Hello", FileSafety::Windows.make_safe("Hello..."));
+    /// assert_eq!("CON_", FileSafety::Windows.make_safe("CON"));
+    /// assert_eq!("_", FileSafety::Posix.make_safe(".."));
+    /// ```
+    pub fn make_safe(self, s: &str) -> Cow<'_, str> {
+        let mapped = self.map_chars(s);
+        let reserved = self.is_reserved(&mapped);
+        let limited = self.truncate(&mapped, MAX_LENGTH - reserved as usize);
+        let trimmed = self.trim(limited);
+
+        if is_unusable(trimmed) {
+            Cow::Borrowed(PLACEHOLDER)
+        } else if reserved {
+            Cow::Owned(format!("{}_", trimmed))
+        } else if trimmed.len() == mapped.len() {
+            mapped
+        } else {
+            Cow::Owned(trimmed.to_string())
+        }
+    }
+
+    /// Makes a filename from a stem and extension, keeping the extension if it has to be
+    /// shortened.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::file_safety::FileSafety;
+    /// assert_eq!("01 - Why_.mp3", FileSafety::Fat32.file_name("01 - Why=", "mp3"));
+    /// assert_eq!("aux_.mp3", FileSafety::Windows.file_name("aux", "mp3"));
+    /// ```
+    pub fn file_name(self, stem: &str, extension: &str) -> String {
+        let mapped = self.map_chars(stem);
+        let reserved = self.is_reserved(&mapped);
+        let limit = MAX_LENGTH - extension.len() - 1 - reserved as usize;
+        let stem = self.trim_start(self.truncate(&mapped, limit));
+        let stem = if is_unusable(stem) { PLACEHOLDER } else { stem };
+        let suffix = if reserved { "_" } else { "" };
+        format!("{}{}.{}", stem, suffix, extension)
+    }
+
+    /// Returns if a name is already safe.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::file_safety::FileSafety;
+    /// assert!(FileSafety::Posix.is_safe("foo: bar"));
+    /// assert!(!FileSafety::Windows.is_safe("foo: bar"));
+    /// ```
+    pub fn is_safe(self, s: &str) -> bool {
+        matches!(self.make_safe(s), Cow::Borrowed(_))
+    }
+
+    fn is_reserved(self, s: &str) -> bool {
+        if self == FileSafety::Posix {
+            return false;
+        }
+        let base = s.split('.').next().unwrap_or(s).trim_end();
+        RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(base))
+    }
+
+    fn truncate(self, s: &str, limit: usize) -> &str {
+        let end = match self {
+            FileSafety::Posix => {
+                let mut end = s.len().min(limit);
+                while !s.is_char_boundary(end) {
+                    end -= 1;
+                }
+                end
+            }
+            _ => s
+                .char_indices()
+                .nth(limit)
+                .map(|(i, _)| i)
+                .unwrap_or(s.len()),
+        };
+        &s[..end]
+    }
+
+    fn trim_start(self, s: &str) -> &str {
+        match self {
+            FileSafety::Posix => s,
+            _ => s.trim_start_matches(' '),
+        }
+    }
+
+    fn trim(self, s: &str) -> &str {
+        match self {
+            FileSafety::Posix => s,
+            _ => self.trim_start(s).trim_end_matches(['.', ' ']),
+        }
+    }
+}
+
+/// Returns if a name is empty or refers to a folder itself, and can't be used.
+fn is_unusable(s: &str) -> bool {
+    matches!(s, "" | "." | "..")
+}
+
+/// Replaces characters in a string, only allocating if something is replaced.
+fn replace_chars<F>(s: Cow<'_, str>, replace: F) -> Cow<'_, str>
+where
+    F: Fn(char) -> Option<&'static str>,
+{
+    if !s.chars().any(|c| replace(c).is_some()) {
+        return s;
+    }
+
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match replace(c) {
+            Some(r) => out.push_str(r),
+            None => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+impl fmt::Display for FileSafety {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for FileSafety {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FileSafety::ALL
+            .into_iter()
+            .find(|f| f.name() == s)
+            .ok_or_else(|| format!("unknown file safety profile \"{}\"", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn made_safe_names_are_safe(s: String) -> bool {
+        FileSafety::ALL
+            .into_iter()
+            .all(|f| f.is_safe(&f.make_safe(&s)))
+    }
+
+    #[quickcheck]
+    fn names_fit_in_the_length_limit(s: String) -> bool {
+        FileSafety::ALL.into_iter().all(|f| {
+            let name = f.file_name(&s, "mp3");
+            match f {
+                FileSafety::Posix => name.len() <= MAX_LENGTH,
+                _ => name.chars().count() <= MAX_LENGTH,
+            }
+        })
+    }
+
+    #[quickcheck]
+    fn strict_ascii_is_only_simple_chars(s: String) -> bool {
+        FileSafety::StrictAscii
+            .make_safe(&s)
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " -_.()".contains(c))
+    }
+
+    #[test]
+    fn posix_keeps_windows_chars() {
+        assert_eq!(
+            "What? \"Yes\": <no>",
+            FileSafety::Posix.make_safe("What? \"Yes\": <no>")
+        );
+    }
+
+    #[test]
+    fn windows_removes_control_chars() {
+        assert_eq!("ab", FileSafety::Windows.make_safe("a\tb"));
+    }
+
+    #[test]
+    fn windows_reserved_names_ignore_case_and_extension() {
+        assert_eq!("com1.txt_", FileSafety::Windows.make_safe("com1.txt"));
+        assert_eq!("Console", FileSafety::Windows.make_safe("Console"));
+    }
+
+    #[test]
+    fn folder_names_are_replaced() {
+        for safety in FileSafety::ALL {
+            for name in ["", ".", ".."] {
+                assert_eq!("_", safety.make_safe(name), "{} {:?}", safety, name);
+            }
+            assert_eq!("_.mp3", safety.file_name("", "mp3"), "{}", safety);
+        }
+    }
+
+    #[test]
+    fn long_names_keep_their_extension() {
+        let name = FileSafety::Fat32.file_name(&"a".repeat(300), "mp3");
+        assert_eq!(MAX_LENGTH, name.len());
+        assert!(name.ends_with("a.mp3"));
+    }
+
+    #[test]
+    fn profiles_are_parsed() {
+        assert_eq!(Ok(FileSafety::StrictAscii), "strict-ascii".parse());
+        assert!("ntfs".parse::<FileSafety>().is_err());
+    }
+}
@@ -1,15 +1,17 @@
 //! Image handling and transformation.
 
-use image::DynamicImage;
+use image::{DynamicImage, RgbImage};
+use serde::{de, ser, Deserialize, Serialize};
 use std::{
+    borrow::Cow,
     convert::{TryFrom, TryInto},
     error::Error,
     fmt, fs,
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 /// An image format.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Format {
     Png,
     Jpeg,
@@ -112,10 +114,10 @@ impl Image {
 
     /// Load an image at a path, taking a cached version if it exists.
     ///
-    /// This function searches for images with the `.png`, `.jpg`, and `.jpeg` file extensions,
-    /// processes their raw data using `process`, and returns the resultant image. It checks for
-    /// pre-processed images in the cache first. If it finds an image that was not in the cache, it
-    /// caches the processed image.
+    /// This function searches for images with the `.png`, `.jpg`, and `.jpeg` file extensions in
+    /// any case, processes their raw data using `process`, and returns the resultant image. It
+    /// checks for pre-processed images in the cache first. If it finds an image that was not in
+    /// the cache, it caches the processed image.
     ///
     /// # Examples
     ///
@@ -135,33 +137,80 @@ impl Image {
         Q: AsRef<Path>,
         F: Fn(DynamicImage) -> Result<Self, image::ImageError>,
     {
-        let images = images.as_ref();
-        let cache = cache.as_ref();
-        let fnames = ["png", "jpg", "jpeg"]
-            .iter()
-            .map(|ext| format!("{}.{}", name, ext))
-            .collect::<Vec<_>>();
+        Self::load_with_cache_as(images, cache, name, name, process)
+    }
 
-        let mut images_paths = fnames.iter().map(|n| images.join(n));
-        let mut cache_paths = fnames.iter().map(|n| cache.join(n));
+    /// Load an image at a path, caching it under a different name.
+    ///
+    /// This works like `load_with_cache`, but looks in the cache for `cache_name` instead of
+    /// `name`. This lets images with the same name be cached separately.
+    pub fn load_with_cache_as<P, Q, F>(
+        images: P,
+        cache: Q,
+        name: &str,
+        cache_name: &str,
+        process: F,
+    ) -> Result<Self, LoadWithCacheError>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: Fn(DynamicImage) -> Result<Self, image::ImageError>,
+    {
+        let cache = cache.as_ref();
 
-        if let Some(path) = cache_paths.find(|p| p.exists()) {
+        if let Some(path) = find_with_name(cache, cache_name) {
             Image::load(path).map_err(LoadWithCacheError::CacheLoadError)
-        } else if let Some(path) = images_paths.find(|p| p.exists()) {
-            let raw = image::open(&path).map_err(LoadWithCacheError::CouldntOpenUncachedImage)?;
-            let image = process(raw).map_err(LoadWithCacheError::ProcessError)?;
-            // Ensure that the cache folder exists.
-            fs::create_dir_all(cache).map_err(LoadWithCacheError::CouldntCreateCacheFolder)?;
-            let output_name = format!("{}.{}", name, image.format.ext());
-            let cache_path = cache.join(output_name);
-            fs::write(cache_path, &image.data[..])
-                .map_err(LoadWithCacheError::CouldntWriteCachedFile)?;
-            Ok(image)
+        } else if let Some(path) = find_with_name(images.as_ref(), name) {
+            Self::process_and_cache(&path, cache, cache_name, process)
         } else {
             Err(LoadWithCacheError::NoImage)
         }
     }
 
+    /// Load a specific image file, taking a cached version if it exists.
+    ///
+    /// Unlike `load_with_cache`, a missing file is an error rather than `NoImage`.
+    pub fn load_file_with_cache<P, Q, F>(
+        path: P,
+        cache: Q,
+        cache_name: &str,
+        process: F,
+    ) -> Result<Self, LoadWithCacheError>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: Fn(DynamicImage) -> Result<Self, image::ImageError>,
+    {
+        let cache = cache.as_ref();
+
+        if let Some(path) = find_with_name(cache, cache_name) {
+            Image::load(path).map_err(LoadWithCacheError::CacheLoadError)
+        } else {
+            Self::process_and_cache(path.as_ref(), cache, cache_name, process)
+        }
+    }
+
+    /// Process an uncached image and save the result in the cache.
+    fn process_and_cache<F>(
+        path: &Path,
+        cache: &Path,
+        cache_name: &str,
+        process: F,
+    ) -> Result<Self, LoadWithCacheError>
+    where
+        F: Fn(DynamicImage) -> Result<Self, image::ImageError>,
+    {
+        let raw = image::open(path).map_err(LoadWithCacheError::CouldntOpenUncachedImage)?;
+        let image = process(raw).map_err(LoadWithCacheError::ProcessError)?;
+        // Ensure that the cache folder exists.
+        fs::create_dir_all(cache).map_err(LoadWithCacheError::CouldntCreateCacheFolder)?;
+        let output_name = format!("{}.{}", cache_name, image.format.ext());
+        let cache_path = cache.join(output_name);
+        fs::write(cache_path, &image.data[..])
+            .map_err(LoadWithCacheError::CouldntWriteCachedFile)?;
+        Ok(image)
+    }
+
     /// Optionally load an image at a path.
     ///
     /// If no image exists cached or uncached, this returns an `Ok` containing a `None`.
@@ -177,7 +226,26 @@ impl Image {
         Q: AsRef<Path>,
         F: Fn(DynamicImage) -> Result<Self, image::ImageError>,
     {
-        match Self::load_with_cache(images, cache, name, process) {
+        Self::try_load_with_cache_as(images, cache, name, name, process)
+    }
+
+    /// Optionally load an image at a path, caching it under a different name.
+    ///
+    /// If no image exists cached or uncached, this returns an `Ok` containing a `None`.
+    /// If any other errors occur, it returns an `Err`.
+    pub fn try_load_with_cache_as<P, Q, F>(
+        images: P,
+        cache: Q,
+        name: &str,
+        cache_name: &str,
+        process: F,
+    ) -> Result<Option<Self>, LoadWithCacheError>
+    where
+        P: AsRef<Path>,
+        Q: AsRef<Path>,
+        F: Fn(DynamicImage) -> Result<Self, image::ImageError>,
+    {
+        match Self::load_with_cache_as(images, cache, name, cache_name, process) {
             Ok(img) => Ok(Some(img)),
             Err(LoadWithCacheError::NoImage) => Ok(None),
             Err(e) => Err(e),
@@ -199,6 +267,56 @@ impl Image {
     }
 }
 
+/// The file extensions images are searched for with.
+pub(crate) const EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+/// Find an image with a name (without extension) in a folder, with an extension in any case.
+pub(crate) fn find_with_name(folder: &Path, name: &str) -> Option<PathBuf> {
+    let exact = EXTENSIONS
+        .iter()
+        .map(|ext| folder.join(format!("{}.{}", name, ext)))
+        .find(|p| p.exists());
+    if exact.is_some() {
+        return exact;
+    }
+
+    // Extensions like "JPG" can only be found by listing the folder on case-sensitive
+    // filesystems.
+    let path = folder.join(name);
+    let stem = path.file_name()?;
+    fs::read_dir(path.parent()?)
+        .ok()?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|p| p.file_stem() == Some(stem))
+        .filter_map(|p| Some((extension_index(&p)?, p)))
+        .min()
+        .map(|(_, p)| p)
+}
+
+/// Gets where an image's extension is in [`EXTENSIONS`], ignoring case, or None if it isn't an
+/// image.
+pub(crate) fn extension_index(path: &Path) -> Option<usize> {
+    let extension = path.extension()?.to_str()?;
+    EXTENSIONS
+        .iter()
+        .position(|e| e.eq_ignore_ascii_case(extension))
+}
+
+/// Get the name of an image file without its folder or extension.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::image::file_stem;
+/// assert_eq!("intro", file_stem("disc 1/intro.png"));
+/// ```
+pub fn file_stem(file: &str) -> Cow<'_, str> {
+    Path::new(file)
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or(Cow::Borrowed(file))
+}
+
 /// An error when loading an image.
 #[derive(Debug)]
 pub enum LoadError {
@@ -239,6 +357,7 @@ pub enum LoadWithCacheError {
     ProcessError(image::ImageError),
     CouldntCreateCacheFolder(std::io::Error),
     CouldntWriteCachedFile(std::io::Error),
+    CouldntReadImagesFolder(std::io::Error),
 }
 
 impl fmt::Display for LoadWithCacheError {
@@ -256,6 +375,9 @@ impl fmt::Display for LoadWithCacheError {
             LoadWithCacheError::CouldntWriteCachedFile(e) => {
                 write!(f, "couldn't write cached file: {}", e)
             }
+            LoadWithCacheError::CouldntReadImagesFolder(e) => {
+                write!(f, "couldn't read images folder: {}", e)
+            }
         }
     }
 }
@@ -268,23 +390,243 @@ impl Error for LoadWithCacheError {
             LoadWithCacheError::CouldntOpenUncachedImage(e)
             | LoadWithCacheError::ProcessError(e) => Some(e),
             LoadWithCacheError::CouldntCreateCacheFolder(e)
-            | LoadWithCacheError::CouldntWriteCachedFile(e) => Some(e),
+            | LoadWithCacheError::CouldntWriteCachedFile(e)
+            | LoadWithCacheError::CouldntReadImagesFolder(e) => Some(e),
+        }
+    }
+}
+
+/// An RGB color, written as `#rrggbb`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Color(pub [u8; 3]);
+
+impl Color {
+    /// Black, the default background color.
+    pub const BLACK: Color = Color([0, 0, 0]);
+
+    /// Gets the color as a hex string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::image::Color;
+    /// assert_eq!("#ff8000", Color([255, 128, 0]).to_hex());
+    /// ```
+    pub fn to_hex(self) -> String {
+        let [r, g, b] = self.0;
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+impl std::str::FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(format!("invalid color \"{}\"", s));
+        }
+
+        let mut rgb = [0; 3];
+        for (i, c) in rgb.iter_mut().enumerate() {
+            *c = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                .map_err(|_| format!("invalid color \"{}\"", s))?;
+        }
+        Ok(Color(rgb))
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// How a cover that isn't square is made to fit a square target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Shape {
+    /// Resize the cover to fit inside the target, keeping its aspect ratio.
+    Fit,
+
+    /// Resize the cover to fill the target, cropping around the center.
+    Crop,
+
+    /// Resize the cover to fit inside the target, padding the rest with the background color.
+    Pad,
+}
+
+/// Settings controlling how covers are processed before being embedded.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CoverSettings {
+    /// The width and height of the target square, in pixels.
+    pub size: u32,
+
+    /// The JPEG quality, from 1 to 100.
+    pub quality: u8,
+
+    /// If JPEGs should use progressive instead of baseline encoding.
+    pub progressive: bool,
+
+    /// How non-square covers are handled.
+    pub shape: Shape,
+
+    /// The color used for padding.
+    pub background: Color,
+
+    /// The output format, or `None` to use whichever format produces smaller output.
+    pub format: Option<Format>,
+}
+
+impl CoverSettings {
+    /// The settings used for covers in the full album.
+    pub fn full() -> Self {
+        Self {
+            size: 1000,
+            quality: 75,
+            progressive: false,
+            shape: Shape::Fit,
+            background: Color::BLACK,
+            format: None,
         }
     }
+
+    /// The settings used for covers in car exports.
+    pub fn vw() -> Self {
+        Self {
+            size: 300,
+            format: Some(Format::Jpeg),
+            ..Self::full()
+        }
+    }
+
+    /// Gets a name that uniquely identifies the settings, for use in cache folders.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::image::CoverSettings;
+    /// assert_eq!("1000-q75-fit", CoverSettings::full().cache_name());
+    /// ```
+    pub fn cache_name(&self) -> String {
+        let mut name = format!("{}-q{}", self.size, self.quality);
+        if self.progressive {
+            name.push_str("-prog");
+        }
+        match self.shape {
+            Shape::Fit => name.push_str("-fit"),
+            Shape::Crop => name.push_str("-crop"),
+            Shape::Pad => {
+                name.push_str("-pad");
+                name.push_str(&self.background.to_hex()[1..]);
+            }
+        }
+        if let Some(format) = self.format {
+            name.push('-');
+            name.push_str(format.ext());
+        }
+        name
+    }
+}
+
+impl Default for CoverSettings {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+fn encode_png(img: &RgbImage) -> Result<Vec<u8>, image::ImageError> {
+    use image::{codecs::png::PngEncoder, ImageEncoder};
+
+    let mut data = Vec::new();
+    PngEncoder::new(&mut data)
+        .write_image(img, img.width(), img.height(), image::ColorType::Rgb8)
+        .map(|()| data)
+}
+
+fn encode_jpeg(
+    img: &RgbImage,
+    quality: u8,
+    progressive: bool,
+) -> Result<Vec<u8>, image::ImageError> {
+    use image::error::{EncodingError, ImageFormatHint};
+
+    fn to_image_error<E>(err: E) -> image::ImageError
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let format = ImageFormatHint::Exact(image::ImageFormat::Jpeg);
+        image::ImageError::Encoding(EncodingError::new(format, err))
+    }
+
+    let width = u16::try_from(img.width()).map_err(to_image_error)?;
+    let height = u16::try_from(img.height()).map_err(to_image_error)?;
+
+    let mut data = Vec::new();
+    let mut encoder = jpeg_encoder::Encoder::new(&mut data, quality);
+    encoder.set_progressive(progressive);
+    encoder
+        .encode(img, width, height, jpeg_encoder::ColorType::Rgb)
+        .map_err(to_image_error)?;
+    Ok(data)
 }
 
-macro_rules! encode {
-    ( $enc:ident, $img:expr ) => {{
-        let mut data = Vec::new();
-        $enc::new(&mut data)
-            .encode(
-                $img,
-                $img.width(),
-                $img.height(),
-                <image::Rgb<u8> as image::PixelWithColorType>::COLOR_TYPE,
-            )
-            .map(|()| data)
-    }};
+/// Transform a cover according to a set of settings.
+///
+/// The transformed image is a square with sides of `settings.size` pixels, unless the shape is
+/// `Shape::Fit`, in which case only its longer side is that length.
+pub fn transform_cover(
+    img: DynamicImage,
+    settings: &CoverSettings,
+) -> Result<Image, image::ImageError> {
+    use image::imageops::{self, FilterType};
+
+    let size = settings.size;
+    let img = match settings.shape {
+        Shape::Fit => img.resize(size, size, FilterType::Lanczos3).to_rgb8(),
+        Shape::Crop => img
+            .resize_to_fill(size, size, FilterType::Lanczos3)
+            .to_rgb8(),
+        Shape::Pad => {
+            let img = img.resize(size, size, FilterType::Lanczos3).to_rgb8();
+            let mut canvas = RgbImage::from_pixel(size, size, image::Rgb(settings.background.0));
+            let x = (size - img.width()) / 2;
+            let y = (size - img.height()) / 2;
+            imageops::overlay(&mut canvas, &img, x.into(), y.into());
+            canvas
+        }
+    };
+
+    let jpeg = || encode_jpeg(&img, settings.quality, settings.progressive);
+
+    Ok(match settings.format {
+        Some(Format::Png) => Image::from_png(encode_png(&img)?),
+        Some(Format::Jpeg) => Image::from_jpeg(jpeg()?),
+        None => {
+            // Try both PNG and JPEG encoding.
+            let png_data = encode_png(&img)?;
+            let jpeg_data = jpeg()?;
+
+            if png_data.len() <= jpeg_data.len() {
+                Image::from_png(png_data)
+            } else {
+                Image::from_jpeg(jpeg_data)
+            }
+        }
+    })
 }
 
 /// Transform an image into a standard format.
@@ -292,37 +634,122 @@ macro_rules! encode {
 /// The transformed image is 1000x1000 pixels, and may be a PNG or JPEG. The encoding used is
 /// whichever produces a smaller-sized output.
 pub fn transform_image(img: DynamicImage) -> Result<Image, image::ImageError> {
-    use image::codecs::{jpeg::JpegEncoder, png::PngEncoder};
+    transform_cover(img, &CoverSettings::full())
+}
 
-    let img = img
-        .resize(1000, 1000, image::imageops::FilterType::Lanczos3)
-        .to_rgb8();
+/// Transform an image into a format for car use.
+pub fn transform_image_vw(img: DynamicImage) -> Result<Image, image::ImageError> {
+    transform_cover(img, &CoverSettings::vw())
+}
 
-    // Try both PNG and JPEG encoding.
-    let png_data = encode!(PngEncoder, &img)?;
-    let jpeg_data = encode!(JpegEncoder, &img)?;
+/// Thresholds used when checking the quality of source images.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImageChecks {
+    /// The smallest width or height a source image should have, in pixels.
+    pub min_size: u32,
 
-    Ok(if png_data.len() <= jpeg_data.len() {
-        Image::from_png(png_data)
-    } else {
-        Image::from_jpeg(jpeg_data)
-    })
+    /// The largest ratio between the longer and shorter sides of a cover.
+    pub max_aspect_ratio: f64,
 }
 
-/// Transform an image into a format for car use.
-pub fn transform_image_vw(img: DynamicImage) -> Result<Image, image::ImageError> {
-    use image::codecs::jpeg::JpegEncoder;
+/// A problem with the quality of a source image.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageWarning {
+    /// The image is smaller than the minimum size, and will be upscaled.
+    TooSmall { width: u32, height: u32, min: u32 },
+
+    /// A cover is far from square.
+    NotSquare { width: u32, height: u32 },
 
-    let img = img
-        .resize(300, 300, image::imageops::FilterType::Lanczos3)
-        .to_rgb8();
-    let data = encode!(JpegEncoder, &img)?;
-    Ok(Image::from_jpeg(data))
+    /// The image is a CMYK JPEG, which many players show with the wrong colors.
+    Cmyk,
+
+    /// The image has transparent pixels, which are flattened to black.
+    Transparent,
+
+    /// The image couldn't be decoded.
+    Unreadable(String),
+}
+
+impl fmt::Display for ImageWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageWarning::TooSmall { width, height, min } => write!(
+                f,
+                "image is only {}x{} pixels (at least {} expected)",
+                width, height, min
+            ),
+            ImageWarning::NotSquare { width, height } => {
+                write!(f, "cover isn't square ({}x{} pixels)", width, height)
+            }
+            ImageWarning::Cmyk => write!(f, "image uses the CMYK color space"),
+            ImageWarning::Transparent => write!(f, "image has transparent pixels"),
+            ImageWarning::Unreadable(e) => write!(f, "couldn't read image: {}", e),
+        }
+    }
+}
+
+/// Check the quality of a source image.
+///
+/// The aspect ratio is only checked if `is_cover` is set, since booklet pages and photos are
+/// rarely square.
+pub fn check_image<P: AsRef<Path>>(
+    path: P,
+    checks: &ImageChecks,
+    is_cover: bool,
+) -> Result<Vec<ImageWarning>, image::ImageError> {
+    let path = path.as_ref();
+    let mut warnings = Vec::new();
+
+    let img = image::open(path)?;
+    let (width, height) = (img.width(), img.height());
+
+    if width.min(height) < checks.min_size {
+        warnings.push(ImageWarning::TooSmall {
+            width,
+            height,
+            min: checks.min_size,
+        });
+    }
+
+    let ratio = f64::from(width.max(height)) / f64::from(width.min(height).max(1));
+    if is_cover && ratio > checks.max_aspect_ratio {
+        warnings.push(ImageWarning::NotSquare { width, height });
+    }
+
+    if is_cmyk_jpeg(path) {
+        warnings.push(ImageWarning::Cmyk);
+    }
+
+    if img.color().has_alpha() && img.to_rgba8().pixels().any(|p| p.0[3] != u8::MAX) {
+        warnings.push(ImageWarning::Transparent);
+    }
+
+    Ok(warnings)
+}
+
+/// Returns if the file at a path is a JPEG using the CMYK color space.
+fn is_cmyk_jpeg(path: &Path) -> bool {
+    use jpeg_decoder::{Decoder, PixelFormat};
+
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+
+    let mut decoder = Decoder::new(std::io::BufReader::new(file));
+    decoder.read_info().is_ok()
+        && matches!(
+            decoder.info().map(|i| i.pixel_format),
+            Some(PixelFormat::CMYK32)
+        )
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{transform_image, transform_image_vw, Image};
+    use super::{
+        transform_cover, transform_image, transform_image_vw, CoverSettings, Format, Image, Shape,
+    };
     use image::{self, DynamicImage};
     use std::{
         fs::{self, File},
@@ -392,4 +819,139 @@ mod tests {
         let new_img = transform_image_vw(img).ok().unwrap().as_dynamic().unwrap();
         assert_eq!(new_img.width(), 300);
     }
+
+    #[test]
+    #[ignore]
+    fn transform_cover_crops_to_square() {
+        let img = DynamicImage::new_rgb8(600, 300);
+        let settings = CoverSettings {
+            shape: Shape::Crop,
+            ..CoverSettings::vw()
+        };
+        let new_img = transform_cover(img, &settings)
+            .unwrap()
+            .as_dynamic()
+            .unwrap();
+        assert_eq!((new_img.width(), new_img.height()), (300, 300));
+    }
+
+    #[test]
+    #[ignore]
+    fn transform_cover_pads_to_square() {
+        let img = DynamicImage::new_rgb8(600, 300);
+        let settings = CoverSettings {
+            shape: Shape::Pad,
+            background: super::Color([255, 255, 255]),
+            format: Some(Format::Png),
+            ..CoverSettings::vw()
+        };
+        let new_img = transform_cover(img, &settings)
+            .unwrap()
+            .as_dynamic()
+            .unwrap();
+        let new_img = new_img.to_rgb8();
+        assert_eq!((new_img.width(), new_img.height()), (300, 300));
+        assert_eq!(new_img.get_pixel(0, 0).0, [255, 255, 255]);
+        assert_eq!(new_img.get_pixel(150, 150).0, [0, 0, 0]);
+    }
+
+    #[test]
+    #[ignore]
+    fn transform_cover_writes_progressive_jpeg() {
+        let img = DynamicImage::new_rgb8(400, 400);
+        let settings = CoverSettings {
+            progressive: true,
+            ..CoverSettings::vw()
+        };
+        let new_img = transform_cover(img, &settings).unwrap();
+        assert_eq!(new_img.format, Format::Jpeg);
+        // Progressive JPEGs use an SOF2 marker.
+        assert!(new_img.data.windows(2).any(|w| w == [0xFF, 0xC2]));
+    }
+
+    mod check_image {
+        use super::super::{check_image, ImageChecks, ImageWarning};
+        use image::{Rgb, RgbImage, Rgba, RgbaImage};
+        use tempfile::tempdir;
+
+        fn checks() -> ImageChecks {
+            ImageChecks {
+                min_size: 100,
+                max_aspect_ratio: 1.1,
+            }
+        }
+
+        #[test]
+        fn large_square_image_has_no_warnings() {
+            let dir = tempdir().unwrap();
+            let path = dir.path().join("a.png");
+            RgbImage::from_pixel(100, 100, Rgb([1, 2, 3]))
+                .save(&path)
+                .unwrap();
+            assert_eq!(
+                Vec::<ImageWarning>::new(),
+                check_image(&path, &checks(), true).unwrap()
+            );
+        }
+
+        #[test]
+        fn small_image_is_too_small() {
+            let dir = tempdir().unwrap();
+            let path = dir.path().join("a.png");
+            RgbImage::new(50, 50).save(&path).unwrap();
+            assert_eq!(
+                vec![ImageWarning::TooSmall {
+                    width: 50,
+                    height: 50,
+                    min: 100
+                }],
+                check_image(&path, &checks(), true).unwrap()
+            );
+        }
+
+        #[test]
+        fn wide_cover_isnt_square() {
+            let dir = tempdir().unwrap();
+            let path = dir.path().join("a.png");
+            RgbImage::new(200, 100).save(&path).unwrap();
+            assert_eq!(
+                vec![ImageWarning::NotSquare {
+                    width: 200,
+                    height: 100
+                }],
+                check_image(&path, &checks(), true).unwrap()
+            );
+        }
+
+        #[test]
+        fn wide_non_cover_has_no_warnings() {
+            let dir = tempdir().unwrap();
+            let path = dir.path().join("a.png");
+            RgbImage::new(200, 100).save(&path).unwrap();
+            assert!(check_image(&path, &checks(), false).unwrap().is_empty());
+        }
+
+        #[test]
+        fn transparent_image_is_transparent() {
+            let dir = tempdir().unwrap();
+            let path = dir.path().join("a.png");
+            RgbaImage::from_pixel(100, 100, Rgba([0, 0, 0, 0]))
+                .save(&path)
+                .unwrap();
+            assert_eq!(
+                vec![ImageWarning::Transparent],
+                check_image(&path, &checks(), true).unwrap()
+            );
+        }
+
+        #[test]
+        fn opaque_image_with_alpha_has_no_warnings() {
+            let dir = tempdir().unwrap();
+            let path = dir.path().join("a.png");
+            RgbaImage::from_pixel(100, 100, Rgba([0, 0, 0, 255]))
+                .save(&path)
+                .unwrap();
+            assert!(check_image(&path, &checks(), true).unwrap().is_empty());
+        }
+    }
 }
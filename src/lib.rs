@@ -3,11 +3,29 @@
 pub mod macros;
 
 pub mod album;
+pub mod articles;
+pub mod artists;
+pub mod artwork;
+pub mod carry;
+pub mod case;
+pub mod comment;
+pub mod covers;
+pub mod credits;
+pub mod custom;
+pub mod date;
 pub mod disc;
+pub mod file_safety;
 pub mod image;
+pub mod language;
+pub mod lint;
+pub mod lyrics;
+pub mod profile;
 pub mod raw;
+pub mod release;
 pub mod text;
 pub mod track;
+pub mod translit;
 pub mod utils;
+pub mod work;
 
 pub use text::Text;
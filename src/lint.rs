@@ -0,0 +1,355 @@
+//! Checks for text that looks the same as what was meant, but isn't.
+
+use crate::{raw, Text};
+use std::{borrow::Cow, fmt};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// A problem found in a piece of text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Issue {
+    /// The text has decomposed characters, like those copied from macOS filenames.
+    NotNfc,
+
+    /// The text contains an invisible character, like a zero-width space.
+    Invisible(char),
+
+    /// The text contains a space other than a regular space, like a non-breaking space or tab.
+    UnusualSpace(char),
+
+    /// The text, or a line in it, starts or ends with whitespace.
+    SurroundingSpace,
+
+    /// The text contains more than one space in a row.
+    RepeatedSpaces,
+
+    /// A word mixes Latin letters with lookalike Cyrillic or Greek letters.
+    MixedScripts(String),
+}
+
+impl Issue {
+    /// Returns if the issue is fixed by [`fix`].
+    pub fn is_fixable(&self) -> bool {
+        !matches!(self, Issue::MixedScripts(_))
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Issue::NotNfc => f.write_str("has decomposed characters"),
+            Issue::Invisible(c) => write!(f, "contains invisible character U+{:04X}", *c as u32),
+            Issue::UnusualSpace(c) => write!(f, "contains unusual space U+{:04X}", *c as u32),
+            Issue::SurroundingSpace => f.write_str("has leading or trailing whitespace"),
+            Issue::RepeatedSpaces => f.write_str("has repeated spaces"),
+            Issue::MixedScripts(word) => {
+                write!(f, "\"{}\" mixes Latin with Cyrillic or Greek letters", word)
+            }
+        }
+    }
+}
+
+/// An issue, with a description of where it is.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Problem {
+    pub location: String,
+    pub issue: Issue,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.issue)
+    }
+}
+
+/// Returns if a character is invisible and almost certainly unintended.
+///
+/// Joiners aren't included, since some scripts and emoji need them.
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00AD}' | '\u{180E}' | '\u{200B}' | '\u{2060}' | '\u{FEFF}'
+    ) || (c.is_control() && !c.is_whitespace())
+        || c == '\r'
+}
+
+/// Returns if a character is whitespace other than a regular space or newline.
+///
+/// Ideographic spaces are allowed, since they're normal in CJK text.
+fn is_unusual_space(c: char) -> bool {
+    c.is_whitespace() && !matches!(c, ' ' | '\n' | '\r' | '\u{3000}')
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Lookalike,
+}
+
+fn script(c: char) -> Option<Script> {
+    match c {
+        'A'..='Z' | 'a'..='z' | '\u{00C0}'..='\u{024F}' => Some(Script::Latin),
+        '\u{0370}'..='\u{03FF}' | '\u{0400}'..='\u{04FF}' => Some(Script::Lookalike),
+        _ => None,
+    }
+}
+
+/// Finds the issues in a string.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::lint::{check, Issue};
+/// assert_eq!(vec![Issue::NotNfc], check("Bjo\u{308}rk"));
+/// assert_eq!(vec![Issue::Invisible('\u{200B}')], check("Sigur\u{200B} Ros"));
+/// assert!(check("Sigur Rós").is_empty());
+/// ```
+pub fn check(s: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    if !is_nfc(s) {
+        issues.push(Issue::NotNfc);
+    }
+    if let Some(c) = s.chars().find(|c| is_invisible(*c)) {
+        issues.push(Issue::Invisible(c));
+    }
+    if let Some(c) = s.chars().find(|c| is_unusual_space(*c)) {
+        issues.push(Issue::UnusualSpace(c));
+    }
+    if s.trim() != s || s.lines().any(|l| l.trim_matches(' ') != l) {
+        issues.push(Issue::SurroundingSpace);
+    }
+    if s.contains("  ") {
+        issues.push(Issue::RepeatedSpaces);
+    }
+    for word in s.split(|c: char| !c.is_alphabetic()) {
+        let mut scripts = word.chars().filter_map(script);
+        if let Some(first) = scripts.next() {
+            if scripts.any(|s| s != first) {
+                issues.push(Issue::MixedScripts(word.to_string()));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Fixes the issues in a string that can be fixed.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::lint::fix;
+/// assert_eq!("Björk", fix(" Bjo\u{308}rk\u{200B}"));
+/// assert_eq!("Sigur Rós", fix("Sigur\u{A0}\u{A0}Rós"));
+/// ```
+pub fn fix(s: &str) -> Cow<'_, str> {
+    if check(s).iter().all(|i| !i.is_fixable()) {
+        return Cow::Borrowed(s);
+    }
+
+    // Invisible characters are removed before composing, since they can keep a letter and
+    // its accent apart.
+    let cleaned = s
+        .chars()
+        .filter(|c| !is_invisible(*c))
+        .map(|c| if is_unusual_space(c) { ' ' } else { c })
+        .collect::<String>();
+
+    let lines = cleaned
+        .trim()
+        .split('\n')
+        .map(|line| {
+            line.split(' ')
+                .filter(|w| !w.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+    Cow::Owned(lines.join("\n").nfc().collect())
+}
+
+/// Finds the issues in a text's value, its ASCII and sort overrides, and its language variants.
+///
+/// Issues in overrides have the override's name, and issues in variants their language.
+pub fn check_text(text: &Text) -> Vec<(Option<&str>, Issue)> {
+    let mut issues = check(text.value())
+        .into_iter()
+        .map(|i| (None, i))
+        .collect::<Vec<_>>();
+    if text.has_overridden_ascii() {
+        issues.extend(check(text.ascii()).into_iter().map(|i| (Some("ascii"), i)));
+    }
+    if let Some(sort) = text.sort_override() {
+        issues.extend(check(sort).into_iter().map(|i| (Some("sort"), i)));
+    }
+    for (language, variant) in text.variants() {
+        issues.extend(check(variant).into_iter().map(|i| (Some(language), i)));
+    }
+    issues
+}
+
+/// Fixes a text's value, its ASCII and sort overrides, and its language variants.
+///
+/// Returns None if the text is locked or has nothing to fix.
+pub fn fix_text(text: &Text) -> Option<Text> {
+    if text.is_locked() {
+        return None;
+    }
+
+    let value = fix(text.value());
+    let ascii = text.has_overridden_ascii().then(|| fix(text.ascii()));
+    let sort = text.sort_override().map(fix);
+    let variants = text
+        .variants()
+        .map(|(language, variant)| (language, fix(variant)))
+        .collect::<Vec<_>>();
+    let changed = |s: &Option<Cow<str>>| matches!(s, Some(Cow::Owned(_)));
+    if matches!(value, Cow::Borrowed(_))
+        && !changed(&ascii)
+        && !changed(&sort)
+        && variants.iter().all(|(_, v)| matches!(v, Cow::Borrowed(_)))
+    {
+        return None;
+    }
+
+    let mut fixed = Text::new(value.into_owned(), ascii.map(Cow::into_owned))
+        .with_sort(sort.map(Cow::into_owned))
+        .with_extras_from(text);
+    for (language, variant) in variants {
+        fixed = fixed.with_variant(language, variant);
+    }
+    Some(fixed)
+}
+
+/// Finds the issues in every text in an album.
+pub fn check_album(album: &raw::Album) -> Vec<Problem> {
+    album
+        .texts()
+        .into_iter()
+        .flat_map(|(location, text)| problems(location, text))
+        .collect()
+}
+
+/// Fixes every text in an album that isn't locked, returning the problems that were found.
+///
+/// Problems that can't be fixed, or are in locked texts, are left as they are.
+pub fn fix_album(album: &mut raw::Album) -> Vec<Problem> {
+    let mut found = Vec::new();
+    for (location, text) in album.texts_mut() {
+        found.extend(problems(location, text));
+        if let Some(fixed) = fix_text(text) {
+            *text = fixed;
+        }
+    }
+    found
+}
+
+fn problems(location: String, text: &Text) -> Vec<Problem> {
+    check_text(text)
+        .into_iter()
+        .map(|(part, issue)| Problem {
+            location: match part {
+                Some(part) => format!("{} {}", location, part),
+                None => location.clone(),
+            },
+            issue,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn fixed_text_only_has_unfixable_issues(s: String) -> bool {
+        check(&fix(&s)).iter().all(|i| !i.is_fixable())
+    }
+
+    #[test]
+    fn accents_split_by_invisible_characters_are_composed() {
+        assert_eq!("\u{E9}", fix("e\u{200B}\u{301}"));
+        assert!(check(&fix("e\u{200B}\u{301}")).is_empty());
+    }
+
+    #[test]
+    fn whitespace_is_found() {
+        assert_eq!(vec![Issue::SurroundingSpace], check("foo "));
+        assert_eq!(vec![Issue::RepeatedSpaces], check("foo  bar"));
+        assert_eq!(vec![Issue::UnusualSpace('\t')], check("foo\tbar"));
+        assert!(check("foo\nbar").is_empty());
+    }
+
+    #[test]
+    fn lookalikes_are_found() {
+        // The "о" is Cyrillic.
+        assert_eq!(
+            vec![Issue::MixedScripts(String::from("Rоck"))],
+            check("Rоck Band")
+        );
+        assert!(check("Мумий Тролль").is_empty());
+    }
+
+    #[test]
+    fn lines_are_fixed_separately() {
+        assert_eq!("one two\nthree", fix("one  two \r\n three\n"));
+    }
+
+    #[test]
+    fn cjk_spaces_are_kept() {
+        assert!(check("東京\u{3000}事変").is_empty());
+    }
+
+    #[test]
+    fn unfixable_text_is_unchanged() {
+        assert_eq!(Cow::Borrowed("Rоck"), fix("Rоck"));
+    }
+
+    #[test]
+    fn locked_text_isnt_fixed() {
+        assert_eq!(None, fix_text(&Text::from("foo  bar").with_lock(true)));
+    }
+
+    #[test]
+    fn overrides_are_fixed() {
+        let text = Text::from(("本", "hon ")).with_sort(Some("ho\u{200B}n"));
+        let fixed = fix_text(&text).unwrap();
+        assert_eq!("hon", fixed.ascii());
+        assert_eq!(Some("hon"), fixed.sort_override());
+    }
+
+    #[test]
+    fn variants_are_fixed() {
+        let text = Text::from("本").with_variant("en", "Book\u{200B}");
+        assert_eq!(
+            vec![(Some("en"), Issue::Invisible('\u{200B}'))],
+            check_text(&text)
+        );
+        assert_eq!(Some("Book"), fix_text(&text).unwrap().variant("en"));
+    }
+
+    #[test]
+    fn album_problems_are_located_and_fixed() {
+        let mut album = raw::Album::new("Title ")
+            .with_artists(vec![Text::from("Artist")])
+            .with_discs(vec![raw::Disc::from_tracks(vec![
+                raw::Track::new("Song"),
+                raw::Track::new("Song").with_lyrics("la  la"),
+            ])]);
+        let expected = vec![
+            Problem {
+                location: String::from("album title"),
+                issue: Issue::SurroundingSpace,
+            },
+            Problem {
+                location: String::from("disc 1 track 2 lyrics"),
+                issue: Issue::RepeatedSpaces,
+            },
+        ];
+        assert_eq!(expected, check_album(&album));
+        assert_eq!(expected, fix_album(&mut album));
+        assert!(check_album(&album).is_empty());
+        assert_eq!("Title", album.title.value());
+    }
+}
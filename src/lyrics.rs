@@ -0,0 +1,251 @@
+//! Lyrics, from the album definition or from files in "extras/lyrics".
+
+use crate::language;
+use serde::de;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// The file extensions lyrics are searched for with, in order of preference.
+pub(crate) const EXTENSIONS: [&str; 2] = ["lrc", "txt"];
+
+/// The language lyrics are tagged with when no language is given.
+pub const DEFAULT_LANGUAGE: &str = "eng";
+
+/// A track's lyrics, with the time each line starts if they're synchronized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lyrics {
+    /// The lyrics without timings.
+    pub text: String,
+
+    /// The time each line starts in milliseconds, with the line, if the lyrics are synchronized.
+    pub synced: Option<Vec<(u32, String)>>,
+}
+
+impl Lyrics {
+    /// Create lyrics without timings.
+    pub fn plain<T: Into<String>>(text: T) -> Self {
+        Self {
+            text: text.into(),
+            synced: None,
+        }
+    }
+
+    /// Parses lyrics in LRC format, like "[00:12.50]First line".
+    ///
+    /// Lines can have several timestamps, and an `[offset:...]` tag shifts every line by that
+    /// many milliseconds. Other tags, like `[ar:...]`, are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::lyrics::Lyrics;
+    /// let lrc = "[ti:Song]\n[00:01.00]Hello\n[00:02.5][00:04.25]Again";
+    /// let lyrics = Lyrics::from_lrc(lrc).unwrap();
+    /// assert_eq!("Hello\nAgain\nAgain", lyrics.text);
+    /// assert_eq!(Some((2500, String::from("Again"))), lyrics.synced.unwrap().get(1).cloned());
+    /// ```
+    pub fn from_lrc(source: &str) -> Result<Self, LrcError> {
+        let mut offset = 0i64;
+        let mut lines = Vec::new();
+
+        for (number, line) in source.lines().enumerate() {
+            let mut rest = line.trim();
+            let mut times = Vec::new();
+            while let Some(tag_end) = rest.strip_prefix('[').and_then(|r| r.find(']')) {
+                let tag = &rest[1..=tag_end];
+                rest = &rest[tag_end + 2..];
+                match tag.split_once(':') {
+                    Some((minutes, _)) if minutes.chars().all(|c| c.is_ascii_digit()) => {
+                        times.push(parse_timestamp(tag).ok_or(LrcError::Timestamp(number + 1))?);
+                    }
+                    Some(("offset", value)) => {
+                        offset = value
+                            .trim()
+                            .parse()
+                            .map_err(|_| LrcError::Offset(number + 1))?;
+                    }
+                    _ => {}
+                }
+            }
+            let text = rest.trim();
+            lines.extend(times.into_iter().map(|time| (time, text.to_string())));
+        }
+
+        if lines.is_empty() {
+            return Err(LrcError::NoTimestamps);
+        }
+
+        // A positive offset makes lines appear sooner.
+        lines.sort_by_key(|&(time, _)| time);
+        let synced = lines
+            .into_iter()
+            .map(|(time, text)| {
+                let time = (i64::from(time) - offset).clamp(0, u32::MAX.into()) as u32;
+                (time, text)
+            })
+            .collect::<Vec<_>>();
+        let text = synced
+            .iter()
+            .map(|(_, text)| text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(Self {
+            text,
+            synced: Some(synced),
+        })
+    }
+
+    /// Loads lyrics from a file, parsing it as LRC if it has an "lrc" extension.
+    pub fn load(path: &Path) -> Result<Self, LyricsError> {
+        let source =
+            fs::read_to_string(path).map_err(|e| LyricsError::CouldntRead(path.into(), e))?;
+        if path.extension().is_some_and(|ext| ext == "lrc") {
+            Self::from_lrc(&source).map_err(|e| LyricsError::InvalidLrc(path.into(), e))
+        } else {
+            Ok(Self::plain(source.trim_end()))
+        }
+    }
+}
+
+/// Parses an LRC timestamp like "01:02.50" into milliseconds.
+fn parse_timestamp(timestamp: &str) -> Option<u32> {
+    let (minutes, seconds) = timestamp.split_once(':')?;
+    let (seconds, fraction) = match seconds.split_once(['.', ':']) {
+        Some((seconds, fraction)) => (seconds, fraction),
+        None => (seconds, "0"),
+    };
+    if seconds.len() != 2 || !(1..=3).contains(&fraction.len()) {
+        return None;
+    }
+
+    let minutes: u32 = minutes.parse().ok()?;
+    let seconds: u32 = seconds.parse().ok().filter(|s| *s < 60)?;
+    // Pad the fraction to milliseconds, so "5" is 500 and "25" is 250.
+    let millis: u32 = format!("{:0<3}", fraction).parse().ok()?;
+    Some((minutes * 60 + seconds) * 1000 + millis)
+}
+
+/// Finds a lyrics file with a name (without extension) in a folder.
+pub(crate) fn find_with_name(folder: &Path, name: &str) -> Option<PathBuf> {
+    EXTENSIONS
+        .iter()
+        .map(|ext| folder.join(format!("{}.{}", name, ext)))
+        .find(|p| p.exists())
+}
+
+/// Returns if a language is written as a three letter ISO 639-2 code, as ID3 requires.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::lyrics::is_language_code;
+/// assert!(is_language_code("deu"));
+/// assert!(!is_language_code("de"));
+/// assert!(!is_language_code("srt"));
+/// ```
+pub fn is_language_code(language: &str) -> bool {
+    language::is_iso_639_2(language)
+}
+
+/// Checks a language from an album definition is a language code.
+pub(crate) fn check_language<E: de::Error>(language: String) -> Result<String, E> {
+    if is_language_code(&language) {
+        Ok(language)
+    } else {
+        Err(E::custom(format_args!(
+            "\"{}\" isn't a three letter ISO 639-2 language code",
+            language
+        )))
+    }
+}
+
+/// An error when parsing LRC lyrics.
+#[derive(Debug, Error)]
+pub enum LrcError {
+    #[error("invalid timestamp on line {0}")]
+    Timestamp(usize),
+
+    #[error("invalid offset on line {0}")]
+    Offset(usize),
+
+    #[error("no lines have timestamps")]
+    NoTimestamps,
+}
+
+/// An error when loading lyrics from a file.
+#[derive(Debug, Error)]
+pub enum LyricsError {
+    #[error("couldn't read {0:?}: {1}")]
+    CouldntRead(PathBuf, io::Error),
+
+    #[error("couldn't parse {0:?}: {1}")]
+    InvalidLrc(PathBuf, LrcError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_are_parsed() {
+        assert_eq!(Some(62_500), parse_timestamp("01:02.50"));
+        assert_eq!(Some(62_005), parse_timestamp("01:02.005"));
+        assert_eq!(Some(62_000), parse_timestamp("1:02"));
+        assert_eq!(None, parse_timestamp("01:72.00"));
+        assert_eq!(None, parse_timestamp("01:2.00"));
+    }
+
+    #[test]
+    fn lines_are_sorted_and_offset() {
+        let lyrics = Lyrics::from_lrc(
+            "
+            [offset:+500]
+            [00:03.00]Second
+            [00:01.00]First
+            [00:00.20]
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            Some(vec![
+                (0, String::new()),
+                (500, String::from("First")),
+                (2500, String::from("Second")),
+            ]),
+            lyrics.synced
+        );
+        assert_eq!("\nFirst\nSecond", lyrics.text);
+    }
+
+    #[test]
+    fn invalid_lrc_doesnt_parse() {
+        assert!(matches!(
+            Lyrics::from_lrc("Just text"),
+            Err(LrcError::NoTimestamps)
+        ));
+        assert!(matches!(
+            Lyrics::from_lrc("[00:01.00]a\n[00:99.00]b"),
+            Err(LrcError::Timestamp(2))
+        ));
+    }
+
+    #[test]
+    fn lyrics_files_are_loaded() {
+        let folder = tempfile::tempdir().unwrap();
+        fs::write(folder.path().join("a.txt"), "Plain\n").unwrap();
+        fs::write(folder.path().join("b.lrc"), "[00:01.00]Timed").unwrap();
+        fs::write(folder.path().join("b.txt"), "Ignored").unwrap();
+
+        let a = find_with_name(folder.path(), "a").unwrap();
+        assert_eq!(Lyrics::plain("Plain"), Lyrics::load(&a).unwrap());
+        let b = find_with_name(folder.path(), "b").unwrap();
+        assert_eq!(
+            Some(vec![(1000, String::from("Timed"))]),
+            Lyrics::load(&b).unwrap().synced
+        );
+        assert_eq!(None, find_with_name(folder.path(), "c"));
+    }
+}
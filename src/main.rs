@@ -2,7 +2,10 @@ extern crate maestro;
 
 use anyhow::{Context, Result as AnyhowResult};
 use indicatif::{ProgressBar, ProgressStyle};
-use maestro::{album::Album, track::Track};
+use maestro::{
+    album::Album, articles::Articles, case, covers, file_safety::FileSafety, image::ImageChecks,
+    lint, profile::ExportFormat, track::Track,
+};
 use std::{fmt::Debug, path::PathBuf};
 use structopt::StructOpt;
 
@@ -24,6 +27,16 @@ struct Opt {
     /// Prints out actions instead of doing them.
     #[structopt(long)]
     dry_run: bool,
+
+    #[structopt(long)]
+    /// Comma separated languages whose articles are moved when sorting, for albums that don't
+    /// set their own. English is used if not specified.
+    articles: Option<Articles>,
+
+    #[structopt(long)]
+    /// The rules used to make the album's filenames safe: posix, windows, fat32 or strict-ascii.
+    /// Defaults to windows.
+    file_safety: Option<FileSafety>,
 }
 
 #[derive(StructOpt, Debug)]
@@ -42,17 +55,33 @@ enum Command {
         /// The root path.
         root: Option<PathBuf>,
 
+        #[structopt(long, default_value = "Compilations")]
+        /// The folder under the root that compilations are exported to.
+        compilations_folder: String,
+
         #[structopt(short, long, default_value = "full")]
         /// The format to export to.
         format: ExportFormat,
 
+        #[structopt(long)]
+        /// The rules used to make the exported filenames safe. Defaults to the album's rules.
+        output_file_safety: Option<FileSafety>,
+
         #[structopt(parse(from_os_str), required_unless("root"))]
         /// The path to write the output to.
         output: Option<PathBuf>,
     },
 
-    /// Validate an album's tags.
-    Validate,
+    /// Validate an album's tags and images.
+    Validate {
+        #[structopt(long)]
+        /// The smallest width or height a source image should have. Defaults to the cover size.
+        min_image_size: Option<u32>,
+
+        #[structopt(long, default_value = "1.1")]
+        /// The largest ratio between the longer and shorter sides of a cover.
+        max_aspect_ratio: f64,
+    },
 
     /// Show the contents of an album.
     Show,
@@ -65,40 +94,69 @@ enum Command {
 
     /// Generate an album definition from a folder of MP3 files.
     Generate,
+
+    /// Check the album definition for invisible characters, decomposed letters and stray spaces.
+    Lint {
+        #[structopt(long)]
+        /// If the issues that can be fixed should be fixed in place.
+        fix: bool,
+    },
+
+    /// Normalize the capitalization of titles and artists in the album definition.
+    Normalize {
+        #[structopt(short, long, default_value = "title")]
+        /// The capitalization style: title or sentence.
+        style: case::CaseStyle,
+
+        #[structopt(long, use_delimiter = true)]
+        /// Comma separated words that are always written as given, like "ABBA".
+        acronyms: Vec<String>,
+    },
+
+    /// Manage an album's cover art.
+    Covers(CoversCommand),
 }
 
 #[derive(StructOpt, Debug)]
-enum ExportFormat {
-    /// Export the full album (keeping ID3 tags and disc folders.)
-    Full,
+#[structopt(rename_all = "kebab-case")]
+enum CoversCommand {
+    /// Extract the artwork embedded in tracks into the images folder.
+    Extract {
+        #[structopt(long)]
+        /// If existing images should be replaced.
+        force: bool,
+    },
 
-    /// Export the album for car use (ASCII tags and flat structure.)
-    Vw,
+    /// List which image each disc and track uses as its cover.
+    List,
 }
 
-impl std::str::FromStr for ExportFormat {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "full" => Ok(Self::Full),
-            "vw" => Ok(Self::Vw),
-            s => Err(format!("Invalid export format \"{}\"", s)),
-        }
+fn load_album(
+    folder: PathBuf,
+    articles: Option<Articles>,
+    file_safety: Option<FileSafety>,
+) -> AnyhowResult<Album> {
+    let mut album = Album::load(folder).context("Couldn't load album")?;
+    if let Some(articles) = articles {
+        album = album.with_default_articles(articles);
     }
+    if let Some(file_safety) = file_safety {
+        album = album.with_file_safety(file_safety);
+    }
+    Ok(album)
 }
 
-fn run_all_tracks<F, E>(folder: PathBuf, action: &'static str, mut func: F) -> AnyhowResult<()>
+fn run_all_tracks<F, E>(album: Album, action: &'static str, mut func: F) -> AnyhowResult<()>
 where
     F: FnMut(&Track) -> Result<(), E>,
     // TODO: Change to Error + Display.
     E: Debug,
 {
-    run_all_tracks_with_ctx(folder, action, |_| (), |_, track| func(track))
+    run_all_tracks_with_ctx(album, action, |_| (), |_, track| func(track))
 }
 
 fn run_all_tracks_with_ctx<F, G, T, E>(
-    folder: PathBuf,
+    album: Album,
     action: &'static str,
     ctx: G,
     mut func: F,
@@ -108,7 +166,6 @@ where
     F: FnMut(&mut T, &Track) -> Result<(), E>,
     E: Debug,
 {
-    let album = Album::load(folder).context("Couldn't load album")?;
     let mut data = ctx(&album);
     let style = ProgressStyle::default_bar()
         .template("{bar} ({pos}/{len}): {msg}")
@@ -143,29 +200,32 @@ fn main() -> AnyhowResult<()> {
         command,
         verbose: _verbose,
         dry_run,
+        articles,
+        file_safety,
     } = Opt::from_args();
 
     match command {
-        Command::Update { force } => {
-            run_all_tracks(folder, "Updating", |track| track.update_id3(force))
-        }
+        Command::Update { force } => run_all_tracks(
+            load_album(folder, articles, file_safety)?,
+            "Updating",
+            |track| track.update_id3(force),
+        ),
         Command::Export {
             format,
             root,
+            compilations_folder,
             output,
+            output_file_safety,
         } => {
+            let album = load_album(folder, articles, file_safety)?;
+            let safety = output_file_safety.unwrap_or_else(|| album.file_safety());
             run_all_tracks_with_ctx(
-                folder,
+                album,
                 "Copying",
                 |album| {
                     let output = output.unwrap_or_else(|| {
                         // TODO: Don't unwrap.
-                        let mut root = root.unwrap();
-                        let artist = album.artist();
-                        let title = album.title();
-                        root.push(artist.file_safe());
-                        root.push(title.file_safe());
-                        root
+                        album.library_path(root.unwrap(), &compilations_folder, safety)
                     });
 
                     // Make sure the output folder exists.
@@ -173,30 +233,83 @@ fn main() -> AnyhowResult<()> {
                     output
                 },
                 |output, track| match format {
-                    ExportFormat::Full => track.export(&output),
-                    ExportFormat::Vw => track.update_id3_vw(&output),
+                    ExportFormat::Full => track.export(&output, safety),
+                    ExportFormat::Vw => track.update_id3_vw(&output, safety),
                 },
             )
         }
-        Command::Validate => run_all_tracks(folder, "Validating", |track| track.validate()),
+        Command::Validate {
+            min_image_size,
+            max_aspect_ratio,
+        } => run_all_tracks_with_ctx(
+            load_album(folder, articles, file_safety)?,
+            "Validating",
+            |album| {
+                let cover = album.profile(ExportFormat::Full).cover;
+                let checks = ImageChecks {
+                    min_size: min_image_size.unwrap_or(cover.size),
+                    max_aspect_ratio,
+                };
+
+                let mut warnings: Vec<(String, String)> = album
+                    .texts_without_ascii()
+                    .into_iter()
+                    .map(|(location, text)| {
+                        let warning = format!("\"{}\" has no ASCII form", text.value());
+                        (location, warning)
+                    })
+                    .collect();
+
+                warnings.extend(
+                    lint::check_album(album.raw())
+                        .into_iter()
+                        .map(|problem| (problem.location, problem.issue.to_string())),
+                );
+
+                match album.check_images(&checks) {
+                    Ok(found) => warnings.extend(
+                        found
+                            .into_iter()
+                            .map(|(file, warning)| (format!("\"{}\"", file), warning.to_string())),
+                    ),
+                    Err(e) => println!("Couldn't check images: {}", e),
+                }
+
+                if !warnings.is_empty() {
+                    println!("Warnings:");
+                    for (location, warning) in warnings {
+                        println!("{}: {}", location, warning);
+                    }
+                }
+            },
+            |_, track| track.validate(),
+        ),
         Command::Show => {
-            let album = Album::load(folder).context("Couldn't load album")?;
+            let album = load_album(folder, articles, file_safety)?;
             let stdout = std::io::stdout();
             serde_yaml::to_writer(stdout, album.raw()).context("Couldn't serialize album to yaml")
             // println!("{:#?}", album);
         }
-        Command::Clear => run_all_tracks(folder, "Clearing", |track| track.clear()),
+        Command::Clear => run_all_tracks(
+            load_album(folder, articles, file_safety)?,
+            "Clearing",
+            |track| track.clear(),
+        ),
         Command::Rename => {
-            run_all_tracks(folder, "Renaming", |track| {
-                // TODO: Move rename() into track.
-                let path = track.path();
-                let can_path = track.canonical_path();
-                if path != can_path && !dry_run {
-                    std::fs::rename(path, can_path)
-                } else {
-                    Ok(())
-                }
-            })
+            run_all_tracks(
+                load_album(folder, articles, file_safety)?,
+                "Renaming",
+                |track| {
+                    // TODO: Move rename() into track.
+                    let path = track.path();
+                    let can_path = track.canonical_path();
+                    if path != can_path && !dry_run {
+                        std::fs::rename(path, can_path)
+                    } else {
+                        Ok(())
+                    }
+                },
+            )
         }
         Command::Generate => {
             use std::fs;
@@ -207,5 +320,92 @@ fn main() -> AnyhowResult<()> {
                 .context("Couldn't create album.yaml")?;
             serde_yaml::to_writer(file, album.raw()).context("Couldn't write album to file")
         }
+        Command::Lint { fix } => {
+            let mut album = load_album(folder, articles, file_safety)?;
+            let problems = if fix && !dry_run {
+                lint::fix_album(album.raw_mut())
+            } else {
+                lint::check_album(album.raw())
+            };
+            for problem in &problems {
+                println!("{}", problem);
+            }
+
+            if !fix || dry_run || problems.is_empty() {
+                return Ok(());
+            }
+            let remaining = lint::check_album(album.raw()).len();
+            println!(
+                "Fixed {} issues, {} left",
+                problems.len() - remaining,
+                remaining
+            );
+            let file = std::fs::File::create(album.extras_path().join("album.yaml"))
+                .context("Couldn't create album.yaml")?;
+            serde_yaml::to_writer(file, album.raw()).context("Couldn't write album to file")
+        }
+        Command::Normalize { style, acronyms } => {
+            let mut album = load_album(folder, articles, file_safety)?;
+            let normalizer = case::Normalizer::new(style).with_acronyms(acronyms);
+            let changes = normalizer.normalize_album(album.raw_mut());
+            for change in &changes {
+                println!("{}", change);
+            }
+
+            if changes.is_empty() || dry_run {
+                return Ok(());
+            }
+            let file = std::fs::File::create(album.extras_path().join("album.yaml"))
+                .context("Couldn't create album.yaml")?;
+            serde_yaml::to_writer(file, album.raw()).context("Couldn't write album to file")
+        }
+        Command::Covers(CoversCommand::Extract { force }) => {
+            let album = load_album(folder, articles, file_safety)?;
+            let images = album.image_path();
+            let extracted = covers::extract_artwork(&album).context("Couldn't extract artwork")?;
+
+            for image in extracted {
+                if let Some(existing) = image.existing(&images) {
+                    if !force {
+                        println!("Skipping \"{}\": {:?} exists", image.name, existing);
+                        continue;
+                    }
+                    if !dry_run {
+                        std::fs::remove_file(&existing)
+                            .with_context(|| format!("Couldn't remove {:?}", existing))?;
+                    }
+                }
+
+                println!("Extracting \"{}\"", image.file_name());
+                if !dry_run {
+                    image
+                        .write_to(&images)
+                        .with_context(|| format!("Couldn't write \"{}\"", image.file_name()))?;
+                }
+            }
+
+            Ok(())
+        }
+        Command::Covers(CoversCommand::List) => {
+            let album = load_album(folder, articles, file_safety)?;
+            let show = |source: Option<covers::CoverSource>| match source {
+                Some(source) => source.to_string(),
+                None => String::from("none"),
+            };
+
+            for disc in album.discs() {
+                println!("Disc {}: {}", disc.disc_number, show(disc.cover_source()));
+                for track in disc.tracks() {
+                    println!(
+                        "  {}. {}: {}",
+                        track.track_number,
+                        track.title().value(),
+                        show(track.cover_source())
+                    );
+                }
+            }
+
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,133 @@
+//! Export profiles.
+
+use crate::{artwork::ArtworkKind, image::CoverSettings, text::Text};
+use std::{borrow::Cow, fmt, str::FromStr};
+
+/// A format an album can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// Export the full album (keeping ID3 tags and disc folders.)
+    Full,
+
+    /// Export the album for car use (ASCII tags and flat structure.)
+    Vw,
+}
+
+impl ExportFormat {
+    /// Gets the name of the format, as used in album definitions and on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Vw => "vw",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "full" => Ok(Self::Full),
+            "vw" => Ok(Self::Vw),
+            s => Err(format!("Invalid export format \"{}\"", s)),
+        }
+    }
+}
+
+/// Settings used when writing an album's files for a particular export format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    /// How covers are processed before being embedded.
+    pub cover: CoverSettings,
+
+    /// The kinds of artwork that are embedded.
+    pub artwork: Vec<ArtworkKind>,
+
+    /// The language variant used for titles, if any.
+    pub variant: Option<String>,
+
+    /// If the title in another language is written to a TXXX frame.
+    pub alternate_title: bool,
+
+    /// If the number of tracks and discs are written along with track and disc numbers.
+    pub totals: bool,
+
+    /// If single-disc albums are still given a disc number.
+    pub single_disc: bool,
+}
+
+impl Profile {
+    /// The built-in profile for the full album.
+    pub fn full() -> Self {
+        Self {
+            cover: CoverSettings::full(),
+            artwork: ArtworkKind::ALL.to_vec(),
+            variant: None,
+            alternate_title: false,
+            totals: true,
+            single_disc: false,
+        }
+    }
+
+    /// The built-in profile for car exports.
+    pub fn vw() -> Self {
+        Self {
+            cover: CoverSettings::vw(),
+            artwork: vec![ArtworkKind::FrontCover],
+            variant: None,
+            alternate_title: false,
+            totals: true,
+            single_disc: false,
+        }
+    }
+
+    /// Returns if a kind of artwork is embedded.
+    pub fn includes(&self, kind: ArtworkKind) -> bool {
+        self.artwork.contains(&kind)
+    }
+
+    /// Gets a text in the profile's language variant.
+    pub fn text<'t>(&self, text: &'t Text) -> Cow<'t, Text> {
+        text.in_variant(self.variant.as_deref())
+    }
+
+    /// Gets the alternate title written alongside a title, if the profile writes one.
+    ///
+    /// This is the original title when a variant is used, and otherwise the first variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::{profile::Profile, Text};
+    /// let title = Text::from("本").with_variant("en", "Book");
+    /// let mut profile = Profile::full();
+    /// profile.alternate_title = true;
+    /// assert_eq!(Some("Book"), profile.alternate_title(&title));
+    /// profile.variant = Some(String::from("en"));
+    /// assert_eq!(Some("本"), profile.alternate_title(&title));
+    /// ```
+    pub fn alternate_title<'t>(&self, title: &'t Text) -> Option<&'t str> {
+        if !self.alternate_title {
+            return None;
+        }
+        match self.variant.as_deref() {
+            Some(variant) if title.variant(variant).is_some() => Some(title.value()),
+            _ => title.variants().next().map(|(_, value)| value),
+        }
+    }
+
+    /// The built-in profile for an export format.
+    pub fn builtin(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Full => Self::full(),
+            ExportFormat::Vw => Self::vw(),
+        }
+    }
+}
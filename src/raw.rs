@@ -1,7 +1,9 @@
 mod album;
 mod disc;
+mod profile;
 mod track;
 
-pub use album::{Album, AlbumYear};
+pub use album::{Album, AlbumDate, VARIOUS_ARTISTS};
 pub use disc::Disc;
+pub use profile::{Cover, Profile, Profiles};
 pub use track::Track;
@@ -1,16 +1,48 @@
-use super::disc::Disc;
-use crate::Text;
+use super::{disc::Disc, profile::Profiles};
+use crate::{
+    articles::Articles,
+    artists::{ArtistFrames, ArtistStyle, SEMICOLON},
+    carry::Carried,
+    credits::Credits,
+    custom::Custom,
+    date::{self, Date, DateVisitor},
+    lyrics,
+    release::{self, Release},
+    Text,
+};
 use id3::TagLike;
 use serde::{de, ser, Deserialize, Serialize};
 use std::{borrow::Cow, fmt, path::Path};
 
+/// The album artist compilations use when they don't name their own.
+pub const VARIOUS_ARTISTS: &str = "Various Artists";
+
 #[derive(Debug)]
 pub struct Album {
     pub title: Text,
     pub artists: Vec<Text>,
-    pub year: Option<AlbumYear>,
+    /// If the album collects tracks by different artists. A release type of compilation doesn't
+    /// imply this, since a best-of can be by a single artist.
+    pub compilation: bool,
+    /// The album's release date, or carry to use the previous track's date.
+    pub date: Option<AlbumDate>,
+    /// The date the album was first released, if this is a reissue.
+    pub original_date: Option<Date>,
     pub genre: Option<Text>,
+    /// The ISO 639-2 code of the language the album is sung in, used to tag lyrics.
+    pub language: Option<String>,
+    /// The people credited on every track, unless a disc or track overrides them.
+    pub credits: Credits,
+    /// Details about the album's release, like its label and catalog number.
+    pub release: Release,
+    /// Custom fields on every track, unless a disc or track removes them.
+    pub custom: Custom,
     pub discs: Vec<Disc>,
+    pub profiles: Profiles,
+    pub articles: Option<Articles>,
+    pub artist_style: ArtistStyle,
+    /// Fields that carry over from the previous track when a track doesn't set them.
+    pub carry: Vec<Carried>,
 }
 
 impl Album {
@@ -22,9 +54,19 @@ impl Album {
         Album {
             title: title.into(),
             artists: Vec::new(),
-            year: None,
+            compilation: false,
+            date: None,
+            original_date: None,
             genre: None,
+            language: None,
+            credits: Credits::default(),
+            release: Release::default(),
+            custom: Custom::default(),
             discs: Vec::new(),
+            profiles: Profiles::default(),
+            articles: None,
+            artist_style: ArtistStyle::default(),
+            carry: Vec::new(),
         }
     }
 
@@ -121,17 +163,70 @@ impl Album {
             value
         }
 
+        // Keep writing artists the way the tags already have them.
+        let semicolons = track_infos
+            .iter()
+            .filter_map(|t| t.tag.as_ref())
+            .flat_map(|t| [t.artist(), t.album_artist()])
+            .flatten()
+            .any(|a| !a.contains('\0') && a.contains(SEMICOLON));
+        let artist_style = ArtistStyle {
+            frames: if semicolons {
+                ArtistFrames::Semicolon
+            } else {
+                ArtistStyle::default().frames
+            },
+            ..ArtistStyle::default()
+        };
+
         let title = get_most_often(&track_infos, id3::Tag::album).map(|s| s.to_string());
-        let artists = vec![get_most_often(&track_infos, id3::Tag::album_artist)
-            .or_else(|| get_most_often(&track_infos, id3::Tag::artist))
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| String::from(""))
-            .into()];
-        let year = get_most_often(&track_infos, |t| t.date_recorded().map(|d| d.year as usize))
-            .map(AlbumYear::Year);
+        let album_artist = get_most_often(&track_infos, id3::Tag::album_artist);
+        let compilation = get_most_often(&track_infos, |t| {
+            t.get("TCMP")?.content().text().map(|c| c == "1")
+        })
+        .unwrap_or(false)
+            || album_artist.is_some_and(|a| a.eq_ignore_ascii_case(VARIOUS_ARTISTS))
+            || (album_artist.is_none()
+                && has_diverse_artists(
+                    track_infos.iter().filter_map(|t| t.tag.as_ref()?.artist()),
+                ));
+        let artists = if compilation {
+            vec![Text::from(VARIOUS_ARTISTS)]
+        } else {
+            album_artist
+                .or_else(|| get_most_often(&track_infos, id3::Tag::artist))
+                .map(split_artists)
+                .unwrap_or_else(|| vec![Text::from("")])
+        };
+        let date = get_most_often(&track_infos, |t| {
+            t.date_released()
+                .or_else(|| t.date_recorded())
+                .and_then(|d| Date::from_timestamp(&d))
+        });
+        let original_date = get_most_often(&track_infos, date::original_release);
         let genre: Option<Text> =
             get_most_often(&track_infos, id3::Tag::genre).map(|s| Text::from(s.to_string()));
 
+        fn extended_text<'a>(tag: &'a id3::Tag, description: &str) -> Option<&'a str> {
+            tag.extended_texts()
+                .find(|t| t.description == description)
+                .map(|t| t.value.as_str())
+        }
+        let text_frame = |id| move |t: &id3::Tag| t.get(id)?.content().text().map(str::to_string);
+        let extended =
+            |description| move |t: &id3::Tag| extended_text(t, description).map(str::to_string);
+        let release = Release {
+            label: get_most_often(&track_infos, text_frame("TPUB")).map(Text::from),
+            catalog_number: get_most_often(&track_infos, extended(release::CATALOG_NUMBER)),
+            barcode: get_most_often(&track_infos, extended(release::BARCODE))
+                .filter(|b| release::is_valid_barcode(b)),
+            release_type: get_most_often(&track_infos, extended(release::RELEASE_TYPE))
+                .and_then(|t| t.parse().ok()),
+            media: get_most_often(&track_infos, text_frame("TMED")),
+            country: get_most_often(&track_infos, extended(release::RELEASE_COUNTRY))
+                .filter(|c| release::is_country_code(c)),
+        };
+
         let mut discs = HashMap::new();
         for info in track_infos.into_iter() {
             let filename = ok_or_print(info.path.strip_prefix(path))
@@ -147,12 +242,13 @@ impl Album {
                 .tag
                 .as_ref()
                 .and_then(|t| t.artist())
-                .map(|a| vec![Text::from(a.to_string())]);
-            let track_year = info
+                .map(split_artists);
+            let track_date = info
                 .tag
                 .as_ref()
-                .and_then(|t| t.date_recorded())
-                .map(|d| d.year as usize);
+                .and_then(|t| t.date_released().or_else(|| t.date_recorded()))
+                .and_then(|d| Date::from_timestamp(&d));
+            let track_original_date = info.tag.as_ref().and_then(date::original_release);
             let track_genre = info
                 .tag
                 .as_ref()
@@ -161,7 +257,8 @@ impl Album {
             let track = Track::new(title.to_string())
                 .with_filename(filename)
                 .with_artists(track_artists)
-                .with_year(track_year)
+                .with_date(track_date)
+                .with_original_date(track_original_date)
                 .with_genre(track_genre);
 
             let disc = info
@@ -183,19 +280,97 @@ impl Album {
 
         Album::new(title.unwrap_or_else(|| String::from("")))
             .with_artists(artists)
-            .with_year(year)
+            .with_artist_style(artist_style)
+            .with_compilation(compilation)
+            .with_date(date.map(AlbumDate::Date))
+            .with_original_date(original_date)
             .with_genre(genre)
+            .with_release(release)
             .with_discs(discs)
     }
 
-    pub fn artist(&self) -> Cow<Text> {
-        crate::utils::comma_separated(&self.artists)
+    pub fn artist(&self) -> Cow<'_, Text> {
+        self.artist_style.join.join(&self.artists)
     }
 
     pub fn genre(&self) -> Option<&Text> {
         self.genre.as_ref()
     }
 
+    /// Returns if a field carries over from the previous track, including dates when the year is
+    /// set to carry.
+    pub fn carries(&self, field: Carried) -> bool {
+        self.carry.contains(&field)
+            || (field == Carried::Date && self.date == Some(AlbumDate::Carry))
+    }
+
+    /// Gets every text in the album and its tracks, with a description of where it is.
+    pub fn texts(&self) -> Vec<(String, &Text)> {
+        let mut texts = vec![(String::from("album title"), &self.title)];
+        texts.extend(
+            self.artists
+                .iter()
+                .map(|a| (String::from("album artist"), a)),
+        );
+        texts.extend(self.genre.iter().map(|g| (String::from("album genre"), g)));
+        texts.extend(
+            self.credits
+                .texts()
+                .into_iter()
+                .map(|(field, text)| (format!("album {}", field), text)),
+        );
+        texts.extend(
+            self.release
+                .label
+                .iter()
+                .map(|l| (String::from("album label"), l)),
+        );
+        for (d, disc) in self.discs.iter().enumerate() {
+            texts.extend(
+                disc.texts()
+                    .into_iter()
+                    .map(|(field, text)| (format!("disc {} {}", d + 1, field), text)),
+            );
+        }
+        texts
+    }
+
+    /// Gets every text in the album and its tracks to change them, with a description of where
+    /// they are.
+    pub fn texts_mut(&mut self) -> Vec<(String, &mut Text)> {
+        let mut texts = vec![(String::from("album title"), &mut self.title)];
+        texts.extend(
+            self.artists
+                .iter_mut()
+                .map(|a| (String::from("album artist"), a)),
+        );
+        texts.extend(
+            self.genre
+                .iter_mut()
+                .map(|g| (String::from("album genre"), g)),
+        );
+        texts.extend(
+            self.credits
+                .texts_mut()
+                .into_iter()
+                .map(|(field, text)| (format!("album {}", field), text)),
+        );
+        texts.extend(
+            self.release
+                .label
+                .iter_mut()
+                .map(|l| (String::from("album label"), l)),
+        );
+        for (d, disc) in self.discs.iter_mut().enumerate() {
+            texts.extend(
+                disc.texts_mut()
+                    .into_iter()
+                    .map(|(field, text)| (format!("disc {} {}", d + 1, field), text)),
+            );
+        }
+        texts
+    }
+
     pub fn num_discs(&self) -> usize {
         self.discs.len()
     }
@@ -214,8 +389,18 @@ impl Album {
         self
     }
 
-    pub fn with_year<T: Into<Option<AlbumYear>>>(mut self, year: T) -> Self {
-        self.year = year.into();
+    pub fn with_compilation(mut self, compilation: bool) -> Self {
+        self.compilation = compilation;
+        self
+    }
+
+    pub fn with_date<T: Into<Option<AlbumDate>>>(mut self, date: T) -> Self {
+        self.date = date.into();
+        self
+    }
+
+    pub fn with_original_date<T: Into<Option<Date>>>(mut self, original_date: T) -> Self {
+        self.original_date = original_date.into();
         self
     }
 
@@ -224,10 +409,50 @@ impl Album {
         self
     }
 
+    pub fn with_language<T: Into<Option<String>>>(mut self, language: T) -> Self {
+        self.language = language.into();
+        self
+    }
+
+    pub fn with_credits(mut self, credits: Credits) -> Self {
+        self.credits = credits;
+        self
+    }
+
+    pub fn with_release(mut self, release: Release) -> Self {
+        self.release = release;
+        self
+    }
+
+    pub fn with_custom(mut self, custom: Custom) -> Self {
+        self.custom = custom;
+        self
+    }
+
     pub fn with_discs<T: Into<Vec<Disc>>>(mut self, discs: T) -> Self {
         self.discs = discs.into();
         self
     }
+
+    pub fn with_profiles(mut self, profiles: Profiles) -> Self {
+        self.profiles = profiles;
+        self
+    }
+
+    pub fn with_articles<T: Into<Option<Articles>>>(mut self, articles: T) -> Self {
+        self.articles = articles.into();
+        self
+    }
+
+    pub fn with_artist_style(mut self, artist_style: ArtistStyle) -> Self {
+        self.artist_style = artist_style;
+        self
+    }
+
+    pub fn with_carry<T: Into<Vec<Carried>>>(mut self, carry: T) -> Self {
+        self.carry = carry.into();
+        self
+    }
 }
 
 impl Serialize for Album {
@@ -238,19 +463,58 @@ impl Serialize for Album {
         use crate::utils::{ser_one_or_more, ser_opt};
         use ser::SerializeStruct;
 
-        let num_fields = [self.year.is_some(), self.genre.is_some()]
-            .iter()
-            .copied()
-            .filter(|x| *x)
-            .count()
+        let num_fields = [
+            self.compilation,
+            self.date.is_some(),
+            self.original_date.is_some(),
+            self.genre.is_some(),
+            self.language.is_some(),
+            !self.credits.is_empty(),
+            !self.release.is_empty(),
+            !self.custom.is_empty(),
+            !self.profiles.is_empty(),
+            self.articles.is_some(),
+            !self.artist_style.is_default(),
+            !self.carry.is_empty(),
+        ]
+        .iter()
+        .copied()
+        .filter(|x| *x)
+        .count()
             + 3;
 
         let mut state = serializer.serialize_struct("Album", num_fields)?;
         state.serialize_field("title", &self.title)?;
         ser_one_or_more(&mut state, &self.artists, "artist", "artists")?;
-        ser_opt(&mut state, self.year, "year")?;
+        if self.compilation {
+            state.serialize_field("compilation", &self.compilation)?;
+        }
+        if let Some(date) = self.date {
+            state.serialize_field(date.key(), &date)?;
+        }
+        ser_opt(&mut state, self.original_date, "original-date")?;
         ser_opt(&mut state, self.genre(), "genre")?;
+        ser_opt(&mut state, self.language.as_deref(), "language")?;
+        if !self.credits.is_empty() {
+            state.serialize_field("credits", &self.credits)?;
+        }
+        if !self.release.is_empty() {
+            state.serialize_field("release", &self.release)?;
+        }
+        if !self.custom.is_empty() {
+            state.serialize_field("custom", &self.custom)?;
+        }
         ser_one_or_more(&mut state, &self.discs, "tracks", "discs")?;
+        if !self.profiles.is_empty() {
+            state.serialize_field("profiles", &self.profiles)?;
+        }
+        ser_opt(&mut state, self.articles.as_ref(), "articles")?;
+        if !self.artist_style.is_default() {
+            state.serialize_field("artist-style", &self.artist_style)?;
+        }
+        if !self.carry.is_empty() {
+            state.serialize_field("carry", &self.carry)?;
+        }
         state.end()
     }
 }
@@ -266,10 +530,23 @@ impl<'de> Deserialize<'de> for Album {
             Title,
             Artists,
             Artist,
+            Compilation,
             Year,
+            Date,
+            #[serde(rename = "original-date")]
+            OriginalDate,
             Genre,
+            Language,
+            Credits,
+            Release,
+            Custom,
             Discs,
             Tracks,
+            Profiles,
+            Articles,
+            #[serde(rename = "artist-style")]
+            ArtistStyle,
+            Carry,
             #[serde(other)]
             Other,
         }
@@ -289,33 +566,70 @@ impl<'de> Deserialize<'de> for Album {
             {
                 let mut title = None;
                 let mut artists = None;
-                let mut year = None;
+                let mut compilation = None;
+                let mut date = None;
+                let mut original_date = None;
                 let mut genre = None;
+                let mut language = None;
+                let mut credits = None;
+                let mut release = None;
+                let mut custom = None;
                 let mut discs = None;
+                let mut profiles = None;
+                let mut articles = None;
+                let mut artist_style = None;
+                let mut carry = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
                         Fields::Title => field!(map, title),
                         Fields::Artists => field!(map, artists),
                         Fields::Artist => field!(artists { vec![map.next_value()?] }),
-                        Fields::Year => field!(map, year),
+                        Fields::Compilation => field!(map, compilation),
+                        Fields::Year | Fields::Date => field!(map, date),
+                        Fields::OriginalDate => field!(map, original_date),
                         Fields::Genre => field!(map, genre),
+                        Fields::Language => field!(language {
+                            lyrics::check_language(map.next_value()?)?
+                        }),
+                        Fields::Credits => field!(map, credits),
+                        Fields::Release => field!(map, release),
+                        Fields::Custom => field!(map, custom),
                         Fields::Discs => field!(map, discs),
                         Fields::Tracks => field!(discs { vec![map.next_value()?] }),
+                        Fields::Profiles => field!(map, profiles),
+                        Fields::Articles => field!(map, articles),
+                        Fields::ArtistStyle => field!(map, artist_style),
+                        Fields::Carry => field!(map, carry),
                         Fields::Other => {}
                     }
                 }
 
                 let title = title.ok_or_else(|| de::Error::missing_field("title"))?;
-                let artists = artists.ok_or_else(|| de::Error::missing_field("artists"))?;
+                let compilation = compilation.unwrap_or(false);
+                let artists = match artists {
+                    Some(artists) => artists,
+                    None if compilation => vec![Text::from(VARIOUS_ARTISTS)],
+                    None => return Err(de::Error::missing_field("artists")),
+                };
                 let discs = discs.ok_or_else(|| de::Error::missing_field("discs"))?;
 
                 Ok(Album {
                     title,
                     artists,
-                    year,
+                    compilation,
+                    date,
+                    original_date,
                     genre,
+                    language,
+                    credits: credits.unwrap_or_default(),
+                    release: release.unwrap_or_default(),
+                    custom: custom.unwrap_or_default(),
                     discs,
+                    profiles: profiles.unwrap_or_default(),
+                    articles,
+                    artist_style: artist_style.unwrap_or_default(),
+                    carry: carry.unwrap_or_default(),
                 })
             }
         }
@@ -324,25 +638,56 @@ impl<'de> Deserialize<'de> for Album {
     }
 }
 
+/// Returns if most tracks have a different artist, as on a compilation. Two artists taking
+/// turns, like on a split, aren't enough.
+fn has_diverse_artists<'a, I: IntoIterator<Item = &'a str>>(artists: I) -> bool {
+    let artists = artists.into_iter().collect::<Vec<_>>();
+    let distinct = artists
+        .iter()
+        .collect::<std::collections::HashSet<_>>()
+        .len();
+    distinct > 2 && distinct * 2 > artists.len()
+}
+
+/// Splits an artist frame with multiple values into its artists, whether they're separated by
+/// nulls or by semicolons.
+fn split_artists(frame: &str) -> Vec<Text> {
+    frame
+        .split('\0')
+        .flat_map(|a| a.split(SEMICOLON))
+        .map(|a| Text::from(a.to_string()))
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AlbumYear {
+pub enum AlbumDate {
     Carry,
-    Year(usize),
+    Date(Date),
+}
+
+impl AlbumDate {
+    /// Gets the key the date is written with in manifests.
+    pub(crate) fn key(&self) -> &'static str {
+        match self {
+            Self::Carry => "year",
+            Self::Date(date) => date.key(),
+        }
+    }
 }
 
-impl Serialize for AlbumYear {
+impl Serialize for AlbumDate {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
         match self {
             Self::Carry => serializer.serialize_str("carry"),
-            Self::Year(year) => serializer.serialize_u64(*year as u64),
+            Self::Date(date) => date.serialize(serializer),
         }
     }
 }
 
-impl<'de> Deserialize<'de> for AlbumYear {
+impl<'de> Deserialize<'de> for AlbumDate {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: de::Deserializer<'de>,
@@ -350,10 +695,10 @@ impl<'de> Deserialize<'de> for AlbumYear {
         struct Visitor;
 
         impl<'de> de::Visitor<'de> for Visitor {
-            type Value = AlbumYear;
+            type Value = AlbumDate;
 
             fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                f.write_str("a year or \"carry\"")
+                f.write_str("a year, an ISO date or \"carry\"")
             }
 
             fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
@@ -361,8 +706,8 @@ impl<'de> Deserialize<'de> for AlbumYear {
                 E: de::Error,
             {
                 match value {
-                    "carry" => Ok(AlbumYear::Carry),
-                    _ => Err(E::unknown_variant(value, &["year", "carry"])),
+                    "carry" => Ok(AlbumDate::Carry),
+                    _ => de::Visitor::visit_str(DateVisitor, value).map(AlbumDate::Date),
                 }
             }
 
@@ -370,7 +715,7 @@ impl<'de> Deserialize<'de> for AlbumYear {
             where
                 E: de::Error,
             {
-                Ok(AlbumYear::Year(value as usize))
+                de::Visitor::visit_u64(DateVisitor, value).map(AlbumDate::Date)
             }
         }
 
@@ -382,6 +727,31 @@ impl<'de> Deserialize<'de> for AlbumYear {
 mod tests {
     use super::*;
 
+    #[test]
+    fn generated_albums_split_semicolon_artists() {
+        let dir = tempfile::tempdir().unwrap();
+        let style = ArtistStyle {
+            frames: ArtistFrames::Semicolon,
+            ..ArtistStyle::default()
+        };
+        let artists = [Text::from("A"), Text::from("B")];
+        for title in ["x", "y"] {
+            let path = dir.path().join(format!("{}.mp3", title));
+            std::fs::write(&path, b"").unwrap();
+            let mut tag = id3::Tag::new();
+            tag.set_title(title);
+            tag.set_album("foo");
+            tag.set_artist(style.frame_value(&artists));
+            tag.write_to_path(&path, id3::Version::Id3v24).unwrap();
+        }
+
+        let album = Album::generate(dir.path());
+        assert_eq!(artists.to_vec(), album.artists);
+        assert_eq!(ArtistFrames::Semicolon, album.artist_style.frames);
+        let track = &album.discs[0].tracks()[0];
+        assert_eq!(Some(&artists[..]), track.artists());
+    }
+
     #[test]
     fn artist_is_only_artist_in_list() {
         let album = Album::new("foo").with_artists(vec![Text::from(("b", "c"))]);
@@ -413,6 +783,42 @@ mod tests {
         assert_eq!(Text::from("foo"), album.title);
     }
 
+    #[test]
+    fn articles_are_parsed() {
+        use crate::articles::{Articles, Language};
+
+        let album = serde_yaml::from_str::<Album>(
+            "
+            title: foo
+            artist: Die Arzte
+            articles: [de]
+            tracks:
+                - a
+            ",
+        )
+        .unwrap();
+        assert_eq!(Some(Articles::new(vec![Language::German])), album.articles);
+    }
+
+    #[test]
+    fn artist_style_is_parsed() {
+        use crate::artists::JoinStyle;
+
+        let album = serde_yaml::from_str::<Album>(
+            "
+            title: foo
+            artists: [a, b]
+            artist-style:
+                join: and
+            tracks:
+                - a
+            ",
+        )
+        .unwrap();
+        assert_eq!(JoinStyle::And, album.artist_style.join);
+        assert_eq!("a and b", album.artist().value());
+    }
+
     #[test]
     fn album_year_is_parsed() {
         let album = serde_yaml::from_str::<Album>(
@@ -426,7 +832,7 @@ mod tests {
             ",
         )
         .unwrap();
-        assert_eq!(Some(AlbumYear::Year(2020)), album.year);
+        assert_eq!(Some(AlbumDate::Date(Date::from_year(2020))), album.date);
     }
 
     #[test]
@@ -442,6 +848,97 @@ mod tests {
             ",
         )
         .unwrap();
-        assert_eq!(Some(AlbumYear::Carry), album.year);
+        assert_eq!(Some(AlbumDate::Carry), album.date);
+    }
+
+    #[test]
+    fn album_dates_are_parsed() {
+        let album = serde_yaml::from_str::<Album>(
+            "
+            title: foo
+            artist: bar
+            date: 2011-09-26
+            original-date: 1997-05
+            tracks:
+                - a
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            Some(AlbumDate::Date("2011-09-26".parse().unwrap())),
+            album.date
+        );
+        assert_eq!(Some("1997-05".parse().unwrap()), album.original_date);
+
+        let yaml = serde_yaml::to_string(&album).unwrap();
+        assert!(yaml.contains("date: 2011-09-26"), "{}", yaml);
+    }
+
+    #[test]
+    fn release_is_parsed() {
+        let album = serde_yaml::from_str::<Album>(
+            "
+            title: OK Computer
+            artist: Radiohead
+            release:
+                label: Parlophone
+                catalog-number: NODATA 02
+                country: GB
+            tracks:
+                - a
+            ",
+        )
+        .unwrap();
+        assert_eq!(Some(Text::from("Parlophone")), album.release.label);
+        assert_eq!(Some("NODATA 02"), album.release.catalog_number.as_deref());
+
+        let yaml = serde_yaml::to_string(&album).unwrap();
+        assert!(yaml.contains("catalog-number: NODATA 02"), "{}", yaml);
+        assert!(!serde_yaml::to_string(&Album::new("foo"))
+            .unwrap()
+            .contains("release"));
+    }
+
+    #[test]
+    fn compilations_default_to_various_artists() {
+        let album = serde_yaml::from_str::<Album>(
+            "
+            title: foo
+            compilation: true
+            tracks:
+                - title: a
+                  artist: b
+            ",
+        )
+        .unwrap();
+        assert!(album.compilation);
+        assert_eq!(vec![Text::from(VARIOUS_ARTISTS)], album.artists);
+
+        let album = serde_yaml::from_str::<Album>("{title: foo, tracks: [a]}");
+        assert!(album.is_err());
+    }
+
+    #[test]
+    fn compilations_are_detected_from_diverse_artists() {
+        assert!(!has_diverse_artists(["a", "b", "a", "b"]));
+        assert!(!has_diverse_artists(["a", "b"]));
+        assert!(!has_diverse_artists(["a", "a", "a", "a", "b", "c"]));
+        assert!(has_diverse_artists(["a", "b", "c", "a", "d"]));
+        assert!(has_diverse_artists(["a", "b", "c"]));
+    }
+
+    #[test]
+    fn year_and_date_cant_both_be_set() {
+        let album = serde_yaml::from_str::<Album>(
+            "
+            title: foo
+            artist: bar
+            year: 2011
+            date: 2011-09-26
+            tracks:
+                - a
+            ",
+        );
+        assert!(album.is_err());
     }
 }
@@ -1,10 +1,25 @@
-use super::track::Track;
-use serde::{Deserialize, Serialize};
+use super::track::{Entries, Entry, Track};
+use crate::{credits::Credits, custom::Custom, date::Date, Text};
+use serde::{de, ser, Deserialize, Serialize};
 
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct Disc {
     tracks: Vec<Track>,
+
+    /// The disc's cover, relative to the images folder, if it isn't named after the disc.
+    cover: Option<String>,
+
+    /// The date the disc was released, if it's different from the album's.
+    date: Option<Date>,
+
+    /// The date the disc was first released, if it's different from the album's.
+    original_date: Option<Date>,
+
+    /// The people credited on the disc's tracks, if they're different from the album's.
+    credits: Credits,
+
+    /// Custom fields on the disc's tracks, added to the album's.
+    custom: Custom,
 }
 
 impl Disc {
@@ -13,7 +28,10 @@ impl Disc {
     }
 
     pub fn from_tracks(tracks: Vec<Track>) -> Disc {
-        Disc { tracks }
+        Disc {
+            tracks,
+            ..Default::default()
+        }
     }
 
     pub fn tracks(&self) -> &[Track] {
@@ -27,12 +45,217 @@ impl Disc {
     pub fn num_tracks(&self) -> usize {
         self.tracks.len()
     }
+
+    pub fn cover(&self) -> Option<&str> {
+        self.cover.as_deref()
+    }
+
+    pub fn date(&self) -> Option<Date> {
+        self.date
+    }
+
+    pub fn original_date(&self) -> Option<Date> {
+        self.original_date
+    }
+
+    pub fn credits(&self) -> &Credits {
+        &self.credits
+    }
+
+    pub fn custom(&self) -> &Custom {
+        &self.custom
+    }
+
+    /// Gets every text in the disc and its tracks, with a description of where it is.
+    pub fn texts(&self) -> Vec<(String, &Text)> {
+        let mut texts = self
+            .credits
+            .texts()
+            .into_iter()
+            .map(|(field, text)| (field.to_string(), text))
+            .collect::<Vec<_>>();
+        for (t, track) in self.tracks.iter().enumerate() {
+            texts.extend(
+                track
+                    .texts()
+                    .into_iter()
+                    .map(|(field, text)| (format!("track {} {}", t + 1, field), text)),
+            );
+        }
+        texts
+    }
+
+    /// Gets every text in the disc and its tracks to change them, with a description of where
+    /// they are.
+    pub fn texts_mut(&mut self) -> Vec<(String, &mut Text)> {
+        let mut texts = self
+            .credits
+            .texts_mut()
+            .into_iter()
+            .map(|(field, text)| (field.to_string(), text))
+            .collect::<Vec<_>>();
+        for (t, track) in self.tracks.iter_mut().enumerate() {
+            texts.extend(
+                track
+                    .texts_mut()
+                    .into_iter()
+                    .map(|(field, text)| (format!("track {} {}", t + 1, field), text)),
+            );
+        }
+        texts
+    }
+
+    pub fn with_cover<T: Into<Option<String>>>(mut self, cover: T) -> Self {
+        self.cover = cover.into();
+        self
+    }
+
+    pub fn with_date<T: Into<Option<Date>>>(mut self, date: T) -> Self {
+        self.date = date.into();
+        self
+    }
+
+    pub fn with_original_date<T: Into<Option<Date>>>(mut self, original_date: T) -> Self {
+        self.original_date = original_date.into();
+        self
+    }
+
+    pub fn with_credits(mut self, credits: Credits) -> Self {
+        self.credits = credits;
+        self
+    }
+
+    pub fn with_custom(mut self, custom: Custom) -> Self {
+        self.custom = custom;
+        self
+    }
+}
+
+impl Serialize for Disc {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use crate::utils::ser_opt;
+        use ser::SerializeStruct;
+
+        let num_fields = [
+            self.cover.is_some(),
+            self.date.is_some(),
+            self.original_date.is_some(),
+            !self.credits.is_empty(),
+            !self.custom.is_empty(),
+        ]
+        .iter()
+        .filter(|x| **x)
+        .count();
+        if num_fields == 0 {
+            return Entries(&self.tracks).serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_struct("Disc", num_fields + 1)?;
+        ser_opt(&mut state, self.cover(), "cover")?;
+        if let Some(date) = self.date {
+            state.serialize_field(date.key(), &date)?;
+        }
+        ser_opt(&mut state, self.original_date, "original-date")?;
+        if !self.credits.is_empty() {
+            state.serialize_field("credits", &self.credits)?;
+        }
+        if !self.custom.is_empty() {
+            state.serialize_field("custom", &self.custom)?;
+        }
+        state.serialize_field("tracks", &Entries(&self.tracks))?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Disc {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        use std::fmt;
+
+        #[derive(Deserialize)]
+        #[serde(field_identifier, rename_all = "lowercase")]
+        enum Fields {
+            Tracks,
+            Cover,
+            Year,
+            Date,
+            #[serde(rename = "original-date")]
+            OriginalDate,
+            Credits,
+            Custom,
+            #[serde(other)]
+            Other,
+        }
+
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Disc;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a disc definition")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut tracks = Vec::new();
+                while let Some(entry) = seq.next_element::<Entry>()? {
+                    tracks.extend(entry.into_tracks());
+                }
+                Ok(Disc::from_tracks(tracks))
+            }
+
+            fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+            where
+                M: de::MapAccess<'de>,
+            {
+                let mut tracks = None;
+                let mut cover = None;
+                let mut date = None;
+                let mut original_date = None;
+                let mut credits = None;
+                let mut custom = None;
+
+                while let Some(key) = map.next_key()? {
+                    match key {
+                        Fields::Tracks => field!(tracks { Entry::flatten(map.next_value()?) }),
+                        Fields::Cover => field!(map, cover),
+                        Fields::Year | Fields::Date => field!(map, date),
+                        Fields::OriginalDate => field!(map, original_date),
+                        Fields::Credits => field!(map, credits),
+                        Fields::Custom => field!(map, custom),
+                        Fields::Other => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                let tracks = tracks.ok_or_else(|| de::Error::missing_field("tracks"))?;
+                Ok(Disc {
+                    tracks,
+                    cover,
+                    date,
+                    original_date,
+                    credits: credits.unwrap_or_default(),
+                    custom: custom.unwrap_or_default(),
+                })
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::text::Text;
 
     #[test]
     fn parsed_disc_has_tracks() {
@@ -57,4 +280,133 @@ mod tests {
 
         assert_eq!(tracks, disc.tracks);
     }
+
+    #[test]
+    fn disc_with_cover_is_parsed() {
+        let disc = serde_yaml::from_str::<Disc>(
+            "
+            cover: disc one.png
+            tracks:
+                - foo
+            ",
+        )
+        .unwrap();
+        assert_eq!(Some("disc one.png"), disc.cover());
+        assert_eq!(&[Track::new("foo")], disc.tracks());
+    }
+
+    #[test]
+    fn disc_with_date_is_serde_equal() {
+        let disc = Disc::from_tracks(vec![Track::new("foo")])
+            .with_date("2011-09".parse::<Date>().unwrap())
+            .with_original_date(Date::from_year(1991));
+        let new_disc: Disc = serde_yaml::to_string(&disc)
+            .and_then(|s| serde_yaml::from_str(&s))
+            .unwrap();
+        assert_eq!(disc, new_disc);
+    }
+
+    #[test]
+    fn disc_with_custom_fields_is_serde_equal() {
+        let track =
+            Track::new("foo").with_custom(Custom::default().with("id", String::from("0042")));
+        let disc = Disc::from_tracks(vec![track])
+            .with_custom(Custom::default().with("source", String::from("vinyl")));
+        let yaml = serde_yaml::to_string(&disc).unwrap();
+        assert_eq!(disc, serde_yaml::from_str(&yaml).unwrap());
+        assert!(yaml.contains("custom"), "{}", yaml);
+    }
+
+    #[test]
+    fn works_are_expanded_into_movements() {
+        let disc = serde_yaml::from_str::<Disc>(
+            "
+            - Overture
+            - work: Symphony No. 9
+              movements:
+                - Allegro ma non troppo
+                - title: Molto vivace
+                  date: 1824
+            - Encore
+            ",
+        )
+        .unwrap();
+        let titles = disc
+            .tracks()
+            .iter()
+            .map(|t| t.title.value())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "Overture",
+                "Allegro ma non troppo",
+                "Molto vivace",
+                "Encore"
+            ],
+            titles
+        );
+        let movement = disc.tracks()[2].movement().unwrap();
+        assert_eq!(
+            (&Text::from("Symphony No. 9"), 2, 2),
+            (&movement.work, movement.number, movement.count)
+        );
+        assert_eq!(None, disc.tracks()[3].movement());
+    }
+
+    #[test]
+    fn works_are_serde_equal() {
+        let disc = serde_yaml::from_str::<Disc>(
+            "
+            - work: A
+              movements: [a, b]
+            - work: B
+              movements: [c]
+            - d
+            ",
+        )
+        .unwrap();
+        let yaml = serde_yaml::to_string(&disc).unwrap();
+        assert_eq!(disc, serde_yaml::from_str(&yaml).unwrap());
+        assert!(yaml.contains("work: B"), "{}", yaml);
+    }
+
+    #[test]
+    fn works_with_the_same_name_stay_apart() {
+        let disc = serde_yaml::from_str::<Disc>(
+            "
+            - work: Sonata
+              movements: [a, b]
+            - work: Sonata
+              movements: [c, d, e]
+            ",
+        )
+        .unwrap();
+        let yaml = serde_yaml::to_string(&disc).unwrap();
+        assert_eq!(disc, serde_yaml::from_str(&yaml).unwrap());
+        assert_eq!(2, yaml.matches("work: Sonata").count(), "{}", yaml);
+    }
+
+    #[test]
+    fn work_needs_movements() {
+        assert!(serde_yaml::from_str::<Disc>("[{work: A}]").is_err());
+        assert!(serde_yaml::from_str::<Disc>("[{work: A, title: a, movements: [b]}]").is_err());
+        assert!(serde_yaml::from_str::<Track>("{work: A, movements: [b]}").is_err());
+    }
+
+    #[test]
+    fn disc_without_cover_serializes_to_list() {
+        use serde_yaml::Value;
+        let disc = Disc::from_tracks(vec![Track::new("foo")]);
+        let yaml = serde_yaml::to_value(&disc).unwrap();
+        assert!(matches!(yaml, Value::Sequence(_)));
+    }
+
+    #[test]
+    fn disc_with_cover_is_serde_equal() {
+        let disc = Disc::from_tracks(vec![Track::new("foo")]).with_cover(String::from("a.png"));
+        let new_disc: Disc = serde_yaml::to_string(&disc)
+            .and_then(|s| serde_yaml::from_str(&s))
+            .unwrap();
+        assert_eq!(disc, new_disc);
+    }
 }
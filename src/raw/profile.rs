@@ -0,0 +1,267 @@
+use crate::{
+    artwork::ArtworkKind,
+    image::{Color, CoverSettings, Shape},
+    profile::{self, ExportFormat},
+};
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// Album-specific overrides for the built-in export profiles.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    /// Overrides for the full album.
+    #[serde(skip_serializing_if = "Profile::is_empty")]
+    pub full: Profile,
+
+    /// Overrides for car exports.
+    #[serde(skip_serializing_if = "Profile::is_empty")]
+    pub vw: Profile,
+}
+
+impl Profiles {
+    /// Returns if no profile has been overridden.
+    pub fn is_empty(&self) -> bool {
+        self.full.is_empty() && self.vw.is_empty()
+    }
+
+    /// Gets the overrides for an export format.
+    pub fn get(&self, format: ExportFormat) -> &Profile {
+        match format {
+            ExportFormat::Full => &self.full,
+            ExportFormat::Vw => &self.vw,
+        }
+    }
+
+    /// Gets the profile for an export format, with any overrides applied.
+    pub fn resolve(&self, format: ExportFormat) -> profile::Profile {
+        self.get(format).apply(profile::Profile::builtin(format))
+    }
+}
+
+/// Overrides for a single export profile.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    /// Overrides for cover processing.
+    #[serde(skip_serializing_if = "Cover::is_empty")]
+    pub cover: Cover,
+
+    /// The kinds of artwork to embed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artwork: Option<Vec<ArtworkKind>>,
+
+    /// The language variant of titles to use, like "en".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+
+    /// If the title in another language is written to a TXXX frame.
+    #[serde(rename = "alternate-title")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alternate_title: Option<bool>,
+
+    /// If the number of tracks and discs are written, like "3/12".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub totals: Option<bool>,
+
+    /// If single-disc albums are still written as disc "1/1".
+    #[serde(rename = "single-disc")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_disc: Option<bool>,
+}
+
+impl Profile {
+    /// Returns if nothing in the profile has been overridden.
+    pub fn is_empty(&self) -> bool {
+        self.cover.is_empty()
+            && self.artwork.is_none()
+            && self.variant.is_none()
+            && self.alternate_title.is_none()
+            && self.totals.is_none()
+            && self.single_disc.is_none()
+    }
+
+    /// Applies the overrides to a profile.
+    pub fn apply(&self, mut profile: profile::Profile) -> profile::Profile {
+        profile.cover = self.cover.apply(profile.cover);
+        if let Some(artwork) = &self.artwork {
+            profile.artwork = artwork.clone();
+        }
+        if let Some(variant) = &self.variant {
+            profile.variant = Some(variant.clone());
+        }
+        if let Some(alternate_title) = self.alternate_title {
+            profile.alternate_title = alternate_title;
+        }
+        if let Some(totals) = self.totals {
+            profile.totals = totals;
+        }
+        if let Some(single_disc) = self.single_disc {
+            profile.single_disc = single_disc;
+        }
+        profile
+    }
+}
+
+/// Overrides for cover processing.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Cover {
+    /// The width and height of the cover, in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(deserialize_with = "de_size")]
+    pub size: Option<u32>,
+
+    /// The JPEG quality, from 1 to 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(deserialize_with = "de_quality")]
+    pub quality: Option<u8>,
+
+    /// If JPEGs should be progressive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progressive: Option<bool>,
+
+    /// How non-square covers are handled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape: Option<Shape>,
+
+    /// The color used for padding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<Color>,
+}
+
+impl Cover {
+    /// Returns if no cover setting has been overridden.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Applies the overrides to a set of cover settings.
+    pub fn apply(&self, settings: CoverSettings) -> CoverSettings {
+        CoverSettings {
+            size: self.size.unwrap_or(settings.size),
+            quality: self.quality.unwrap_or(settings.quality),
+            progressive: self.progressive.unwrap_or(settings.progressive),
+            shape: self.shape.unwrap_or(settings.shape),
+            background: self.background.unwrap_or(settings.background),
+            format: settings.format,
+        }
+    }
+}
+
+fn de_size<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match u32::deserialize(deserializer)? {
+        0 => Err(de::Error::custom("cover size must be positive")),
+        size => Ok(Some(size)),
+    }
+}
+
+fn de_quality<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match u8::deserialize(deserializer)? {
+        quality @ 1..=100 => Ok(Some(quality)),
+        _ => Err(de::Error::custom("JPEG quality must be between 1 and 100")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_profiles_resolve_to_builtins() {
+        let profiles = Profiles::default();
+        assert_eq!(
+            profile::Profile::full(),
+            profiles.resolve(ExportFormat::Full)
+        );
+        assert_eq!(profile::Profile::vw(), profiles.resolve(ExportFormat::Vw));
+    }
+
+    #[test]
+    fn overrides_only_change_set_fields() {
+        let profiles = serde_yaml::from_str::<Profiles>(
+            "
+            vw:
+                cover:
+                    quality: 90
+                    shape: pad
+                    background: '#ffffff'
+            ",
+        )
+        .unwrap();
+        let cover = profiles.resolve(ExportFormat::Vw).cover;
+        assert_eq!(300, cover.size);
+        assert_eq!(90, cover.quality);
+        assert_eq!(Shape::Pad, cover.shape);
+        assert_eq!(Color([255, 255, 255]), cover.background);
+        assert_eq!(
+            CoverSettings::full(),
+            profiles.resolve(ExportFormat::Full).cover
+        );
+    }
+
+    #[test]
+    fn artwork_kinds_are_overridden() {
+        let profiles = serde_yaml::from_str::<Profiles>(
+            "
+            vw:
+                artwork: [front-cover, back-cover]
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            vec![ArtworkKind::FrontCover, ArtworkKind::BackCover],
+            profiles.resolve(ExportFormat::Vw).artwork
+        );
+    }
+
+    #[test]
+    fn variant_is_only_set_for_its_profile() {
+        let profiles = serde_yaml::from_str::<Profiles>(
+            "
+            vw:
+                variant: en
+                alternate-title: true
+            ",
+        )
+        .unwrap();
+        let vw = profiles.resolve(ExportFormat::Vw);
+        assert_eq!(Some("en"), vw.variant.as_deref());
+        assert!(vw.alternate_title);
+        assert_eq!(None, profiles.resolve(ExportFormat::Full).variant);
+    }
+
+    #[test]
+    fn totals_are_overridden() {
+        let profiles = serde_yaml::from_str::<Profiles>(
+            "
+            full:
+                single-disc: true
+            vw:
+                totals: false
+            ",
+        )
+        .unwrap();
+        let full = profiles.resolve(ExportFormat::Full);
+        assert!(full.totals && full.single_disc);
+        let vw = profiles.resolve(ExportFormat::Vw);
+        assert!(!vw.totals && !vw.single_disc);
+    }
+
+    #[test]
+    fn out_of_range_quality_doesnt_parse() {
+        let profiles = serde_yaml::from_str::<Profiles>("full: {cover: {quality: 101}}");
+        assert!(profiles.is_err());
+    }
+
+    #[test]
+    fn zero_size_doesnt_parse() {
+        let profiles = serde_yaml::from_str::<Profiles>("full: {cover: {size: 0}}");
+        assert!(profiles.is_err());
+    }
+}
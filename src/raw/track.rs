@@ -1,5 +1,14 @@
-use crate::Text;
+use crate::{
+    comment::{self, Comment},
+    credits::Credits,
+    custom::Custom,
+    date::Date,
+    lyrics,
+    work::Movement,
+    Text,
+};
 use serde::{de, ser, Deserialize, Serialize};
+use std::fmt;
 
 /// A music track in an album.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -10,23 +19,42 @@ pub struct Track {
     /// A list of artists that created the track, or None if the album's artists should be used.
     artists: Option<Vec<Text>>,
 
-    /// The year the track was created, or None if the album's year should be used.
-    pub year: Option<usize>,
+    /// The date the track was released, or None if the disc's or album's date should be used.
+    pub date: Option<Date>,
+
+    /// The date the track was first released, or None if the disc's or album's should be used.
+    pub original_date: Option<Date>,
 
     /// The genre of the track, or None if the album's genre should be used.
     genre: Option<Text>,
 
-    /// Any comments on the track.
-    comment: Option<Text>,
+    /// Any comments on the track, or None if the previous track's should be carried.
+    comments: Option<Vec<Comment>>,
 
     /// The track's lyrics.
     lyrics: Option<Text>,
 
+    /// The ISO 639-2 code of the language the track is sung in, or None if the album's
+    /// language should be used.
+    language: Option<String>,
+
     /// Artists a track features.
     featuring: Option<Vec<Text>>,
 
+    /// The people credited on the track, for any roles its disc's or album's don't cover.
+    credits: Credits,
+
+    /// Custom fields, added to its disc's and album's.
+    custom: Custom,
+
     /// The track's filename, if it isn't derived from the title.
     filename: Option<String>,
+
+    /// The track's cover, relative to the images folder, if it isn't named after the title.
+    cover: Option<String>,
+
+    /// The work the track is a movement of, if it's listed in a work's movements.
+    movement: Option<Movement>,
 }
 
 impl Track {
@@ -38,12 +66,18 @@ impl Track {
         Track {
             title: title.into(),
             artists: None,
-            year: None,
+            date: None,
+            original_date: None,
             genre: None,
-            comment: None,
+            comments: None,
             lyrics: None,
+            language: None,
             featuring: None,
+            credits: Credits::default(),
+            custom: Custom::default(),
             filename: None,
+            cover: None,
+            movement: None,
         }
     }
 
@@ -51,33 +85,98 @@ impl Track {
         self.artists.as_deref()
     }
 
+    pub fn artists_mut(&mut self) -> Option<&mut [Text]> {
+        self.artists.as_deref_mut()
+    }
+
     pub fn genre(&self) -> Option<&Text> {
         self.genre.as_ref()
     }
 
-    pub fn comment(&self) -> Option<&Text> {
-        self.comment.as_ref()
+    pub fn comments(&self) -> Option<&[Comment]> {
+        self.comments.as_deref()
     }
 
     pub fn lyrics(&self) -> Option<&Text> {
         self.lyrics.as_ref()
     }
 
+    pub fn language(&self) -> Option<&str> {
+        self.language.as_deref()
+    }
+
     pub fn featuring(&self) -> Option<&[Text]> {
         self.featuring.as_deref()
     }
 
+    pub fn credits(&self) -> &Credits {
+        &self.credits
+    }
+
+    pub fn custom(&self) -> &Custom {
+        &self.custom
+    }
+
     pub fn filename(&self) -> Option<&str> {
         self.filename.as_deref()
     }
 
+    pub fn cover(&self) -> Option<&str> {
+        self.cover.as_deref()
+    }
+
+    pub fn movement(&self) -> Option<&Movement> {
+        self.movement.as_ref()
+    }
+
+    /// Gets every text in the track, with the name of the field it's in.
+    pub fn texts(&self) -> Vec<(&'static str, &Text)> {
+        let mut texts = vec![("title", &self.title)];
+        texts.extend(self.artists.iter().flatten().map(|a| ("artist", a)));
+        texts.extend(self.genre.iter().map(|g| ("genre", g)));
+        texts.extend(self.comments.iter().flatten().map(|c| ("comment", &c.text)));
+        texts.extend(self.lyrics.iter().map(|l| ("lyrics", l)));
+        texts.extend(self.featuring.iter().flatten().map(|f| ("featuring", f)));
+        texts.extend(self.credits.texts());
+        texts.extend(self.movement.iter().map(|m| ("work", &m.work)));
+        texts
+    }
+
+    /// Gets every text in the track to change them, with the name of the field they're in.
+    pub fn texts_mut(&mut self) -> Vec<(&'static str, &mut Text)> {
+        let mut texts = vec![("title", &mut self.title)];
+        texts.extend(self.artists.iter_mut().flatten().map(|a| ("artist", a)));
+        texts.extend(self.genre.iter_mut().map(|g| ("genre", g)));
+        texts.extend(
+            self.comments
+                .iter_mut()
+                .flatten()
+                .map(|c| ("comment", &mut c.text)),
+        );
+        texts.extend(self.lyrics.iter_mut().map(|l| ("lyrics", l)));
+        texts.extend(
+            self.featuring
+                .iter_mut()
+                .flatten()
+                .map(|f| ("featuring", f)),
+        );
+        texts.extend(self.credits.texts_mut());
+        texts.extend(self.movement.iter_mut().map(|m| ("work", &mut m.work)));
+        texts
+    }
+
     pub fn with_artists<T: Into<Option<Vec<Text>>>>(mut self, artists: T) -> Self {
         self.artists = artists.into();
         self
     }
 
-    pub fn with_year<T: Into<Option<usize>>>(mut self, year: T) -> Self {
-        self.year = year.into();
+    pub fn with_date<T: Into<Option<Date>>>(mut self, date: T) -> Self {
+        self.date = date.into();
+        self
+    }
+
+    pub fn with_original_date<T: Into<Option<Date>>>(mut self, original_date: T) -> Self {
+        self.original_date = original_date.into();
         self
     }
 
@@ -87,7 +186,12 @@ impl Track {
     }
 
     pub fn with_comment<T: Into<Text>>(mut self, comment: T) -> Self {
-        self.comment = Some(comment.into());
+        self.comments = Some(vec![Comment::new(comment)]);
+        self
+    }
+
+    pub fn with_comments<T: Into<Option<Vec<Comment>>>>(mut self, comments: T) -> Self {
+        self.comments = comments.into();
         self
     }
 
@@ -96,16 +200,41 @@ impl Track {
         self
     }
 
+    pub fn with_language<T: Into<Option<String>>>(mut self, language: T) -> Self {
+        self.language = language.into();
+        self
+    }
+
     pub fn with_featuring<T: Into<Option<Vec<Text>>>>(mut self, featuring: T) -> Self {
         self.featuring = featuring.into();
         self
     }
 
+    pub fn with_credits(mut self, credits: Credits) -> Self {
+        self.credits = credits;
+        self
+    }
+
+    pub fn with_custom(mut self, custom: Custom) -> Self {
+        self.custom = custom;
+        self
+    }
+
     pub fn with_filename<T: Into<Option<String>>>(mut self, filename: T) -> Self {
         self.filename = filename.into();
         self
     }
 
+    pub fn with_cover<T: Into<Option<String>>>(mut self, cover: T) -> Self {
+        self.cover = cover.into();
+        self
+    }
+
+    pub fn with_movement<T: Into<Option<Movement>>>(mut self, movement: T) -> Self {
+        self.movement = movement.into();
+        self
+    }
+
     // TODO: Does this function do anything?
     // pub fn simplified(&self, artists: &[Text], year: Option<usize>, genre: Option<&Text>) -> Self {
     //     fn simplify(t: &Text) -> Text {
@@ -161,12 +290,17 @@ impl Serialize for Track {
 
         let num_fields = [
             self.artists.is_some(),
-            self.year.is_some(),
+            self.date.is_some(),
+            self.original_date.is_some(),
             self.genre.is_some(),
-            self.comment.is_some(),
+            self.comments.is_some(),
             self.lyrics.is_some(),
+            self.language.is_some(),
             self.featuring.is_some(),
+            !self.credits.is_empty(),
+            !self.custom.is_empty(),
             self.filename.is_some(),
+            self.cover.is_some(),
         ]
         .iter()
         .copied()
@@ -174,7 +308,7 @@ impl Serialize for Track {
         .count()
             + 1;
 
-        if num_fields == 1 && !self.title.has_overridden_ascii() {
+        if num_fields == 1 && self.title.is_plain() {
             return serializer.serialize_str(self.title.value());
         }
 
@@ -183,14 +317,27 @@ impl Serialize for Track {
         if let Some(artists) = self.artists() {
             ser_one_or_more(&mut state, artists, "artist", "artists")?;
         }
-        ser_opt(&mut state, self.year, "year")?;
+        if let Some(date) = self.date {
+            state.serialize_field(date.key(), &date)?;
+        }
+        ser_opt(&mut state, self.original_date, "original-date")?;
         ser_opt(&mut state, self.genre(), "genre")?;
-        ser_opt(&mut state, self.comment(), "comment")?;
+        if let Some(comments) = self.comments() {
+            ser_one_or_more(&mut state, comments, "comment", "comments")?;
+        }
         ser_opt(&mut state, self.lyrics(), "lyrics")?;
+        ser_opt(&mut state, self.language(), "language")?;
         if let Some(feat) = self.featuring() {
             ser_one_or_more(&mut state, feat, "featuring", "featuring")?;
         }
+        if !self.credits.is_empty() {
+            state.serialize_field("credits", &self.credits)?;
+        }
+        if !self.custom.is_empty() {
+            state.serialize_field("custom", &self.custom)?;
+        }
         ser_opt(&mut state, self.filename(), "filename")?;
+        ser_opt(&mut state, self.cover(), "cover")?;
         state.end()
     }
 }
@@ -200,105 +347,259 @@ impl<'de> Deserialize<'de> for Track {
     where
         D: de::Deserializer<'de>,
     {
-        use std::fmt;
-
-        #[derive(Deserialize)]
-        #[serde(field_identifier, rename_all = "lowercase")]
-        enum Fields {
-            Title,
-            Artists,
-            Artist,
-            Year,
-            Genre,
-            Comment,
-            Lyrics,
-            Featuring,
-            Filename,
-            #[serde(other)]
-            Other,
+        match deserializer.deserialize_any(EntryVisitor)? {
+            Entry::Track(track) => Ok(*track),
+            Entry::Work(..) => Err(de::Error::custom(
+                "works can only be listed in a disc's tracks",
+            )),
         }
+    }
+}
 
-        struct Visitor;
-
-        impl<'de> de::Visitor<'de> for Visitor {
-            type Value = Track;
+/// An item in a disc's track list: a track, or a work and its movements.
+pub(crate) enum Entry {
+    Track(Box<Track>),
+    Work(Text, Vec<Track>),
+}
 
-            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                f.write_str("a track definition")
+impl Entry {
+    /// Expands the entry into its tracks, numbering a work's movements.
+    pub(crate) fn into_tracks(self) -> Vec<Track> {
+        match self {
+            Entry::Track(track) => vec![*track],
+            Entry::Work(work, movements) => {
+                let count = movements.len();
+                movements
+                    .into_iter()
+                    .zip(1..)
+                    .map(|(track, number)| {
+                        track.with_movement(Movement {
+                            work: work.clone(),
+                            number,
+                            count,
+                        })
+                    })
+                    .collect()
             }
+        }
+    }
 
-            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-            where
-                E: de::Error,
-            {
-                Ok(Track::new(value.to_string()))
-            }
+    /// Expands a track list into its tracks.
+    pub(crate) fn flatten(entries: Vec<Entry>) -> Vec<Track> {
+        entries.into_iter().flat_map(Entry::into_tracks).collect()
+    }
+}
+
+impl<'de> Deserialize<'de> for Entry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(EntryVisitor)
+    }
+}
+
+/// Serializes a track list, grouping the movements of each work back into a block.
+pub(crate) struct Entries<'a>(pub &'a [Track]);
 
-            fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+impl Serialize for Entries<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use ser::{SerializeSeq, SerializeStruct};
+
+        struct Work<'a>(&'a Text, &'a [Track]);
+
+        impl Serialize for Work<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
             where
-                E: de::Error,
+                S: ser::Serializer,
             {
-                Ok(Track::new(value))
+                let mut state = serializer.serialize_struct("Work", 2)?;
+                state.serialize_field("work", self.0)?;
+                state.serialize_field("movements", self.1)?;
+                state.end()
             }
+        }
 
-            fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
-            where
-                M: de::MapAccess<'de>,
-            {
-                let mut title = None;
-                let mut artists = None;
-                let mut year = None;
-                let mut genre = None;
-                let mut comment = None;
-                let mut lyrics = None;
-                let mut featuring = None;
-                let mut filename = None;
-
-                while let Some(key) = map.next_key()? {
-                    match key {
-                        Fields::Title => field!(map, title),
-                        Fields::Artists => field!(map, artists),
-                        Fields::Artist => field!(artists { vec![map.next_value()?] }),
-                        Fields::Year => field!(map, year),
-                        Fields::Genre => field!(map, genre),
-                        Fields::Comment => field!(map, comment),
-                        Fields::Lyrics => field!(map, lyrics),
-                        Fields::Featuring => field!(featuring {
-                            // TODO: Make this generic so we can reuse it for other things (like listing artists.)
-                            #[derive(Deserialize)]
-                            #[serde(untagged)]
-                            enum TextOrList {
-                                Text(Text),
-                                List(Vec<Text>),
-                            }
-
-                            let value: TextOrList = map.next_value()?;
-                            match value {
-                                TextOrList::Text(t) => vec![t],
-                                TextOrList::List(l) => l,
-                            }
-                        }),
-                        Fields::Filename => field!(map, filename),
-                        Fields::Other => {}
-                    }
+        let mut seq = serializer.serialize_seq(None)?;
+        let mut rest = self.0;
+        while let Some(first) = rest.first() {
+            let first = match first.movement() {
+                None => {
+                    seq.serialize_element(first)?;
+                    rest = &rest[1..];
+                    continue;
                 }
-
-                let title = title.ok_or_else(|| de::Error::missing_field("title"))?;
-
-                Ok(Track {
-                    title,
-                    artists,
-                    year,
-                    genre,
-                    comment,
-                    lyrics,
-                    featuring,
-                    filename,
+                Some(movement) => movement,
+            };
+            // Works with the same name can follow each other, so the block ends when the
+            // numbering does.
+            let len = rest
+                .iter()
+                .zip(first.number..)
+                .take_while(|(t, number)| {
+                    t.movement().is_some_and(|m| {
+                        m.work == first.work && m.count == first.count && m.number == *number
+                    })
                 })
+                .count();
+            seq.serialize_element(&Work(&first.work, &rest[..len]))?;
+            rest = &rest[len..];
+        }
+        seq.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Fields {
+    Title,
+    Artists,
+    Artist,
+    Year,
+    Date,
+    #[serde(rename = "original-date")]
+    OriginalDate,
+    Genre,
+    Comment,
+    Comments,
+    Lyrics,
+    Language,
+    Featuring,
+    Credits,
+    Custom,
+    Filename,
+    Cover,
+    Work,
+    Movements,
+    #[serde(other)]
+    Other,
+}
+
+struct EntryVisitor;
+
+impl<'de> de::Visitor<'de> for EntryVisitor {
+    type Value = Entry;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a track definition")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Entry::Track(Box::new(Track::new(value.to_string()))))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Entry::Track(Box::new(Track::new(value))))
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+    where
+        M: de::MapAccess<'de>,
+    {
+        let mut title = None;
+        let mut artists = None;
+        let mut date = None;
+        let mut original_date = None;
+        let mut genre = None;
+        let mut comments = None;
+        let mut lyrics = None;
+        let mut language = None;
+        let mut featuring = None;
+        let mut credits = None;
+        let mut custom = None;
+        let mut filename = None;
+        let mut cover = None;
+        let mut work = None;
+        let mut movements = None;
+
+        while let Some(key) = map.next_key()? {
+            match key {
+                Fields::Title => field!(map, title),
+                Fields::Artists => field!(map, artists),
+                Fields::Artist => field!(artists { vec![map.next_value()?] }),
+                Fields::Year | Fields::Date => field!(map, date),
+                Fields::OriginalDate => field!(map, original_date),
+                Fields::Genre => field!(map, genre),
+                Fields::Comments => field!(map, comments),
+                Fields::Comment => field!(comments { vec![map.next_value()?] }),
+                Fields::Lyrics => field!(map, lyrics),
+                Fields::Language => field!(language {
+                    lyrics::check_language(map.next_value()?)?
+                }),
+                Fields::Featuring => field!(featuring {
+                    // TODO: Make this generic so we can reuse it for other things (like listing artists.)
+                    #[derive(Deserialize)]
+                    #[serde(untagged)]
+                    enum TextOrList {
+                        Text(Text),
+                        List(Vec<Text>),
+                    }
+
+                    let value: TextOrList = map.next_value()?;
+                    match value {
+                        TextOrList::Text(t) => vec![t],
+                        TextOrList::List(l) => l,
+                    }
+                }),
+                Fields::Credits => field!(map, credits),
+                Fields::Custom => field!(map, custom),
+                Fields::Filename => field!(map, filename),
+                Fields::Cover => field!(map, cover),
+                Fields::Work => field!(map, work),
+                Fields::Movements => field!(map, movements),
+                Fields::Other => {}
+            }
+        }
+
+        match (work, movements) {
+            (Some(work), Some(movements)) if title.is_none() => {
+                return Ok(Entry::Work(work, movements))
+            }
+            (Some(_), Some(_)) => {
+                return Err(de::Error::custom(
+                    "a work's movements have titles, not the work",
+                ))
             }
+            (Some(_), None) => return Err(de::Error::missing_field("movements")),
+            (None, Some(_)) => return Err(de::Error::missing_field("work")),
+            (None, None) => {}
         }
 
-        deserializer.deserialize_any(Visitor)
+        let title = title.ok_or_else(|| de::Error::missing_field("title"))?;
+        if comments
+            .as_deref()
+            .is_some_and(|c| !comment::are_distinct(c))
+        {
+            return Err(de::Error::custom(
+                "comments need different descriptions or languages",
+            ));
+        }
+
+        Ok(Entry::Track(Box::new(Track {
+            title,
+            artists,
+            date,
+            original_date,
+            genre,
+            comments,
+            lyrics,
+            language,
+            featuring,
+            credits: credits.unwrap_or_default(),
+            custom: custom.unwrap_or_default(),
+            filename,
+            cover,
+            movement: None,
+        })))
     }
 }
 
@@ -425,7 +726,21 @@ mod tests {
             ",
         )
         .unwrap();
-        assert_eq!(Some(1990), track.year);
+        assert_eq!(Some(Date::from_year(1990)), track.date);
+    }
+
+    #[test]
+    fn dates_are_parsed() {
+        let track = serde_yaml::from_str::<Track>(
+            "
+            title: foo
+            date: 1990-03-02
+            original-date: 1989
+            ",
+        )
+        .unwrap();
+        assert_eq!(Some("1990-03-02".parse().unwrap()), track.date);
+        assert_eq!(Some(Date::from_year(1989)), track.original_date);
     }
 
     #[test]
@@ -463,7 +778,7 @@ mod tests {
             ",
         )
         .unwrap();
-        assert_eq!(Some(&Text::from("stuff")), track.comment());
+        assert_eq!(Some(&[Comment::new("stuff")][..]), track.comments());
     }
 
     #[test]
@@ -477,7 +792,59 @@ mod tests {
             ",
         )
         .unwrap();
-        assert_eq!(Some(&Text::from(("stuff", "other"))), track.comment());
+        assert_eq!(
+            Some(&[Comment::new(("stuff", "other"))][..]),
+            track.comments()
+        );
+    }
+
+    #[test]
+    fn title_only_tracks_keep_their_title_details() {
+        let tracks = serde_yaml::from_str::<Vec<Track>>(
+            "
+            - plain
+            - title: {text: iTunes, lock: true}
+            - title: {text: The The, sort: The The}
+            - title: {text: a, file-safe: b}
+            - title: {text: 本, ascii: hon, en: Book}
+            ",
+        )
+        .unwrap();
+        let yaml = serde_yaml::to_string(&tracks).unwrap();
+        assert_eq!(tracks, serde_yaml::from_str::<Vec<Track>>(&yaml).unwrap());
+        assert!(yaml.starts_with("- plain\n"), "{}", yaml);
+
+        let album = serde_yaml::from_str::<crate::raw::Album>(
+            "{title: foo, artist: bar, tracks: [{title: {text: iTunes, lock: true}}]}",
+        )
+        .unwrap();
+        let yaml = serde_yaml::to_string(&album).unwrap();
+        let album = serde_yaml::from_str::<crate::raw::Album>(&yaml).unwrap();
+        assert!(album.discs[0].tracks()[0].title.is_locked());
+    }
+
+    #[test]
+    fn comments_are_serde_equal() {
+        let track = serde_yaml::from_str::<Track>(
+            "
+            title: foo
+            comments:
+                - stuff
+                - text: more
+                  description: source
+            ",
+        )
+        .unwrap();
+        assert_eq!(2, track.comments().unwrap().len());
+        let new_track: Track = serde_yaml::to_string(&track)
+            .and_then(|s| serde_yaml::from_str(&s))
+            .unwrap();
+        assert_eq!(track, new_track);
+    }
+
+    #[test]
+    fn duplicate_comments_dont_parse() {
+        assert!(serde_yaml::from_str::<Track>("{title: foo, comments: [a, b]}").is_err());
     }
 
     #[test]
@@ -506,6 +873,13 @@ mod tests {
         assert_eq!(Some(&Text::from(("stuff", "other"))), track.lyrics());
     }
 
+    #[test]
+    fn language_must_be_a_code() {
+        let track = serde_yaml::from_str::<Track>("{title: foo, language: deu}").unwrap();
+        assert_eq!(Some("deu"), track.language());
+        assert!(serde_yaml::from_str::<Track>("{title: foo, language: German}").is_err());
+    }
+
     #[test]
     fn single_simple_featuring_is_parsed() {
         let track = serde_yaml::from_str::<Track>(
@@ -566,4 +940,16 @@ mod tests {
             track.featuring()
         );
     }
+
+    #[test]
+    fn cover_is_parsed() {
+        let track = serde_yaml::from_str::<Track>(
+            "
+            title: foo
+            cover: intro-disc1.png
+            ",
+        )
+        .unwrap();
+        assert_eq!(Some("intro-disc1.png"), track.cover());
+    }
 }
@@ -0,0 +1,275 @@
+//! Details about an album's release, like its label and catalog number.
+
+use crate::Text;
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::{fmt, str::FromStr};
+
+/// The kind of release an album is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseType {
+    Album,
+    Ep,
+    Single,
+    /// A collection of earlier releases, like a best-of. It's independent of an album's
+    /// `compilation` flag, which means the tracks are by various artists.
+    Compilation,
+    Live,
+    Soundtrack,
+}
+
+impl ReleaseType {
+    /// Gets the name of the release type, as used in album definitions and tags.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Album => "album",
+            Self::Ep => "ep",
+            Self::Single => "single",
+            Self::Compilation => "compilation",
+            Self::Live => "live",
+            Self::Soundtrack => "soundtrack",
+        }
+    }
+}
+
+impl fmt::Display for ReleaseType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ReleaseType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "album" => Ok(Self::Album),
+            "ep" => Ok(Self::Ep),
+            "single" => Ok(Self::Single),
+            "compilation" => Ok(Self::Compilation),
+            "live" => Ok(Self::Live),
+            "soundtrack" => Ok(Self::Soundtrack),
+            _ => Err(format!("Invalid release type \"{}\"", s)),
+        }
+    }
+}
+
+/// Details about an album's release.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Release {
+    /// The record label, written to TPUB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<Text>,
+
+    /// The label's catalog number.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog_number: Option<String>,
+
+    /// The UPC or EAN barcode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(deserialize_with = "de_barcode")]
+    pub barcode: Option<String>,
+
+    /// The kind of release.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "type")]
+    pub release_type: Option<ReleaseType>,
+
+    /// The media the album was released on, like "CD" or "Digital Media", written to TMED.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+
+    /// The ISO 3166 code of the country it was released in, or "XW" for worldwide.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(deserialize_with = "de_country")]
+    pub country: Option<String>,
+}
+
+/// The descriptions of the TXXX frames release details are written to.
+pub const CATALOG_NUMBER: &str = "CATALOGNUMBER";
+pub const BARCODE: &str = "BARCODE";
+pub const RELEASE_TYPE: &str = "RELEASETYPE";
+pub const RELEASE_COUNTRY: &str = "RELEASECOUNTRY";
+
+impl Release {
+    /// Returns if no release details are set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Gets the frame ID, name and contents of the standard frames release details are written
+    /// to.
+    pub fn text_frames(&self) -> [(&'static str, &'static str, Option<&str>); 2] {
+        [
+            ("TPUB", "label", self.label.as_ref().map(Text::value)),
+            ("TMED", "media", self.media.as_deref()),
+        ]
+    }
+
+    /// Gets the TXXX description, name and contents of the user frames release details are
+    /// written to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::release::{Release, ReleaseType};
+    /// let release = Release {
+    ///     release_type: Some(ReleaseType::Ep),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     ("RELEASETYPE", "release type", Some("ep")),
+    ///     release.extended_texts()[2]
+    /// );
+    /// ```
+    pub fn extended_texts(&self) -> [(&'static str, &'static str, Option<&str>); 4] {
+        [
+            (
+                CATALOG_NUMBER,
+                "catalog number",
+                self.catalog_number.as_deref(),
+            ),
+            (BARCODE, "barcode", self.barcode.as_deref()),
+            (
+                RELEASE_TYPE,
+                "release type",
+                self.release_type.map(ReleaseType::name),
+            ),
+            (RELEASE_COUNTRY, "release country", self.country.as_deref()),
+        ]
+    }
+}
+
+/// Returns if a barcode is a UPC-A, EAN-8 or EAN-13 with a correct check digit.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::release::is_valid_barcode;
+/// assert!(is_valid_barcode("5099902894225"));
+/// assert!(is_valid_barcode("724384960650"));
+/// assert!(!is_valid_barcode("5099902894226"));
+/// ```
+pub fn is_valid_barcode(barcode: &str) -> bool {
+    if !matches!(barcode.len(), 8 | 12 | 13) || !barcode.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+
+    // Digits are weighted 3 and 1 alternately, starting with 3 next to the check digit.
+    let sum: u32 = barcode
+        .bytes()
+        .rev()
+        .skip(1)
+        .map(|b| u32::from(b - b'0'))
+        .zip([3, 1].iter().cycle())
+        .map(|(digit, weight)| digit * weight)
+        .sum();
+    let check = u32::from(barcode.as_bytes()[barcode.len() - 1] - b'0');
+    (sum + check).is_multiple_of(10)
+}
+
+/// Returns if a country is written as an uppercase ISO 3166 code, like "GB" or "XW".
+pub fn is_country_code(country: &str) -> bool {
+    country.len() == 2 && country.bytes().all(|b| b.is_ascii_uppercase())
+}
+
+fn de_barcode<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u64),
+        String(String),
+    }
+
+    let barcode = match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(n) => n.to_string(),
+        NumberOrString::String(s) => s,
+    };
+    if is_valid_barcode(&barcode) {
+        Ok(Some(barcode))
+    } else {
+        Err(de::Error::custom(format_args!(
+            "\"{}\" isn't a valid UPC or EAN barcode",
+            barcode
+        )))
+    }
+}
+
+fn de_country<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let country = String::deserialize(deserializer)?;
+    if is_country_code(&country) {
+        Ok(Some(country))
+    } else {
+        Err(de::Error::custom(format_args!(
+            "\"{}\" isn't a two letter country code",
+            country
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn release_is_parsed() {
+        let release: Release = serde_yaml::from_str(
+            "
+            label: Parlophone
+            catalog-number: 7243 8 49606 5 0
+            barcode: 724384960650
+            type: album
+            media: CD
+            country: GB
+            ",
+        )
+        .unwrap();
+        assert_eq!(Some(Text::from("Parlophone")), release.label);
+        assert_eq!(Some("724384960650"), release.barcode.as_deref());
+        assert_eq!(Some(ReleaseType::Album), release.release_type);
+        assert_eq!(Some("GB"), release.country.as_deref());
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        let release = serde_yaml::from_str::<Release>("{label: Warp, distributor: PIAS}").unwrap();
+        assert_eq!(Some(Text::from("Warp")), release.label);
+    }
+
+    #[test]
+    fn invalid_details_dont_parse() {
+        for yaml in [
+            "barcode: 724384960651",
+            "barcode: '1234'",
+            "country: gb",
+            "type: bootleg",
+        ] {
+            assert!(serde_yaml::from_str::<Release>(yaml).is_err(), "{}", yaml);
+        }
+    }
+
+    #[test]
+    fn leading_zeros_are_kept_in_quoted_barcodes() {
+        let release: Release = serde_yaml::from_str("barcode: '0724384960650'").unwrap();
+        assert_eq!(Some("0724384960650"), release.barcode.as_deref());
+    }
+
+    #[test]
+    fn release_round_trips() {
+        let release = Release {
+            label: Some(Text::from(("東芝EMI", "Toshiba EMI"))),
+            catalog_number: Some(String::from("TOCP-65000")),
+            release_type: Some(ReleaseType::Single),
+            ..Default::default()
+        };
+        let yaml = serde_yaml::to_string(&release).unwrap();
+        assert_eq!(release, serde_yaml::from_str(&yaml).unwrap());
+    }
+}
@@ -1,7 +1,9 @@
-use crate::utils::make_file_safe;
+use crate::{articles::Articles, file_safety::FileSafety, language, utils::make_file_safe};
 use serde::{de, ser, Deserialize, Serialize};
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::ops::{Add, AddAssign};
+use std::{convert::Infallible, fmt, str::FromStr};
 
 /// A piece of text with different representations.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -15,6 +17,15 @@ pub struct Text {
     /// A version of the text safe to use in filenames, if it's different from the ASCII
     /// version.
     file_safe: Option<String>,
+
+    /// An overridden name to sort the text by.
+    sort: Option<Cow<'static, str>>,
+
+    /// If the text has been tuned by hand, and shouldn't be normalized.
+    locked: bool,
+
+    /// The text in other languages, keyed by language code.
+    variants: BTreeMap<String, String>,
 }
 
 /// The empty text. Useful for string concatenation.
@@ -34,6 +45,9 @@ impl Text {
             value: Cow::Borrowed(value),
             ascii: Ascii::Same,
             file_safe: None,
+            sort: None,
+            locked: false,
+            variants: BTreeMap::new(),
         }
     }
 
@@ -71,7 +85,100 @@ impl Text {
             value,
             ascii,
             file_safe,
+            sort: None,
+            locked: false,
+            variants: BTreeMap::new(),
+        }
+    }
+
+    /// Start building a `Text` with overridden representations.
+    pub fn builder<T: Into<Cow<'static, str>>>(value: T) -> TextBuilder {
+        TextBuilder {
+            value: value.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Join texts together with a separator, allocating each representation once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// let texts = [Text::from("a"), Text::from(("b", "c")), Text::from("d")];
+    /// let joined = Text::join(&texts, &Text::from(" / "));
+    /// assert_eq!("a / b / d", joined.value());
+    /// assert_eq!("a / c / d", joined.ascii());
+    /// ```
+    pub fn join<'a, I>(texts: I, separator: &Text) -> Text
+    where
+        I: IntoIterator<Item = &'a Text>,
+    {
+        let texts = texts.into_iter().collect::<Vec<_>>();
+        let (first, rest) = match texts.split_first() {
+            Some(split) => split,
+            None => return EMPTY_TEXT,
+        };
+
+        let len =
+            texts.iter().map(|t| t.value.len()).sum::<usize>() + separator.value.len() * rest.len();
+        let mut joined = (*first).clone();
+        joined.value.to_mut().reserve(len - first.value.len());
+        for text in rest {
+            joined += separator;
+            joined += *text;
         }
+        joined
+    }
+
+    /// Override the name the text is sorted by.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// let text = Text::from("The The").with_sort(Some("The The"));
+    /// assert_eq!("The The", text.sortable_file_safe());
+    /// ```
+    pub fn with_sort<T>(mut self, sort: Option<T>) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.sort = sort.map(Into::into);
+        self
+    }
+
+    /// Lock the text, so it's never changed by normalization.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// let text = Text::from("iTunes").with_lock(true);
+    /// assert!(text.is_locked());
+    /// ```
+    pub fn with_lock(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    /// Add a version of the text in another language, such as a translated title.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// let text = Text::from(("本", "hon")).with_variant("en", "Book");
+    /// assert_eq!(Some("Book"), text.variant("en"));
+    /// assert_eq!(None, text.variant("de"));
+    /// ```
+    pub fn with_variant<L, T>(mut self, language: L, value: T) -> Self
+    where
+        L: Into<String>,
+        T: Into<String>,
+    {
+        self.variants.insert(language.into(), value.into());
+        self
     }
 
     /// Create a new `Text` from regular text without an override.
@@ -130,6 +237,33 @@ impl Text {
         self.file_safe.as_deref().unwrap_or_else(|| self.ascii())
     }
 
+    /// Get a representation of the text that's safe as a whole name, following the given rules.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::{file_safety::FileSafety, Text};
+    /// let text = Text::from("AC/DC: Live");
+    /// assert_eq!("AC-DC: Live", text.file_safe_for(FileSafety::Posix));
+    /// assert_eq!("AC-DC - Live", text.file_safe_for(FileSafety::Windows));
+    /// ```
+    pub fn file_safe_for(&self, safety: FileSafety) -> Cow<'_, str> {
+        safety.make_safe(self.file_safe_source(safety))
+    }
+
+    /// Get the text that file safe names are made from with the given rules.
+    ///
+    /// This is the overridden file safe representation if there is one, and otherwise the ASCII
+    /// representation.
+    pub fn file_safe_source(&self, safety: FileSafety) -> &str {
+        // The stored file safe version already has the Windows characters replaced.
+        if safety == FileSafety::Windows || self.file_safe_override().is_some() {
+            self.file_safe()
+        } else {
+            self.ascii()
+        }
+    }
+
     /// Get a sortable filename safe representation of the text.
     ///
     /// # Examples
@@ -139,15 +273,124 @@ impl Text {
     /// let text = Text::from("the bók");
     /// assert_eq!("bok, the", text.sortable_file_safe());
     /// ```
-    pub fn sortable_file_safe(&self) -> Cow<str> {
-        use crate::utils::split_article;
+    pub fn sortable_file_safe(&self) -> Cow<'_, str> {
+        self.sortable_file_safe_with(&Articles::default())
+    }
+
+    /// Get a sortable filename safe representation of the text, moving the given articles.
+    ///
+    /// An overridden sort name is used as-is, apart from being made file safe.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// # use maestro::articles::{Articles, Language};
+    /// let articles = Articles::new(vec![Language::German]);
+    /// assert_eq!("Arzte, Die", Text::from("Die Ärzte").sortable_file_safe_with(&articles));
+    /// ```
+    pub fn sortable_file_safe_with(&self, articles: &Articles) -> Cow<'_, str> {
+        if let Some(sort) = &self.sort {
+            let ascii = calculate_ascii(sort)
+                .map(Cow::Owned)
+                .unwrap_or(Cow::Borrowed(&**sort));
+            return match make_file_safe(&ascii) {
+                Some(safe) => safe.into(),
+                None => ascii,
+            };
+        }
 
         let file_safe = self.file_safe();
-        if let Some((article, rest)) = split_article(file_safe) {
-            format!("{}, {}", rest, article).into()
-        } else {
-            file_safe.into()
+        match articles.split(file_safe) {
+            Some((article, rest)) => format!("{}, {}", rest, article).into(),
+            None => file_safe.into(),
+        }
+    }
+
+    /// Get the name the text is sorted by, moving a leading English article to the end.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// assert_eq!("Beatles, The", Text::from("The Beatles").sort_name());
+    /// assert_eq!("Björk", Text::from("Björk").sort_name());
+    /// ```
+    pub fn sort_name(&self) -> Cow<'_, str> {
+        self.sort_name_with(&Articles::default())
+    }
+
+    /// Get the name the text is sorted by, moving the given articles.
+    ///
+    /// Unlike [`Text::sortable_file_safe_with`], this keeps the text's non-ASCII characters.
+    pub fn sort_name_with(&self, articles: &Articles) -> Cow<'_, str> {
+        match &self.sort {
+            Some(sort) => Cow::Borrowed(sort),
+            None => match articles.split(&self.value) {
+                Some((article, rest)) => format!("{}, {}", rest, article).into(),
+                None => Cow::Borrowed(&self.value),
+            },
+        }
+    }
+
+    /// Get the overridden sort name, if there is one.
+    pub fn sort_override(&self) -> Option<&str> {
+        self.sort.as_deref()
+    }
+
+    /// Get the version of the text in another language, if it has one.
+    pub fn variant(&self, language: &str) -> Option<&str> {
+        self.variants.get(language).map(String::as_str)
+    }
+
+    /// Get the versions of the text in other languages, with their language codes.
+    pub fn variants(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.variants.iter().map(|(l, v)| (l.as_str(), v.as_str()))
+    }
+
+    /// Get the text to use for a language variant, or the text itself if it has no such variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// let text = Text::from(("本", "hon")).with_variant("en", "Book");
+    /// assert_eq!("Book", text.in_variant(Some("en")).value());
+    /// assert_eq!("hon", text.in_variant(Some("de")).ascii());
+    /// assert_eq!("本", text.in_variant(None).value());
+    /// ```
+    pub fn in_variant(&self, language: Option<&str>) -> Cow<'_, Text> {
+        match language.and_then(|l| self.variant(l)) {
+            Some(value) => Cow::Owned(Text::from(value.to_string()).with_lock(self.locked)),
+            None => Cow::Borrowed(self),
+        }
+    }
+
+    /// Return if the text is locked against normalization.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Copy the overridden filename safe representation and the language variants of another
+    /// text, such as one this text was cleaned up from.
+    pub(crate) fn with_extras_from(mut self, other: &Text) -> Self {
+        if let Some(file_safe) = other.file_safe_override() {
+            self.file_safe = Some(file_safe.to_string());
         }
+        self.variants = other.variants.clone();
+        self
+    }
+
+    /// Get the filename safe representation if it's overridden, rather than calculated from the
+    /// ASCII representation.
+    fn file_safe_override(&self) -> Option<&str> {
+        let file_safe = self.file_safe.as_deref()?;
+        (Some(file_safe) != make_file_safe(self.ascii()).as_deref()).then_some(file_safe)
+    }
+
+    /// Get the sort name, or the value if it isn't overridden.
+    fn sort_or_value(&self) -> &str {
+        self.sort.as_deref().unwrap_or(&self.value)
     }
 
     /// Return if the text's ASCII representation has been manually overridden.
@@ -169,6 +412,40 @@ impl Text {
         self.ascii.is_overridden()
     }
 
+    /// Return if the text has nothing but its value, so it's written as a plain string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// assert!(Text::from("iTunes").is_plain());
+    /// assert!(!Text::from("iTunes").with_lock(true).is_plain());
+    /// ```
+    pub fn is_plain(&self) -> bool {
+        !self.has_overridden_ascii()
+            && self.file_safe_override().is_none()
+            && self.sort.is_none()
+            && !self.locked
+            && self.variants.is_empty()
+    }
+
+    /// Return if the text has content, but its ASCII representation is blank.
+    ///
+    /// This happens with scripts that can't be transliterated, and means the text needs an
+    /// ASCII override.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::Text;
+    /// assert!(Text::from("本").has_empty_ascii());
+    /// assert!(!Text::from(("本", "hon")).has_empty_ascii());
+    /// assert!(!Text::from("").has_empty_ascii());
+    /// ```
+    pub fn has_empty_ascii(&self) -> bool {
+        !self.value.trim().is_empty() && self.ascii().trim().is_empty()
+    }
+
     /// Return the simplified text.
     ///
     /// # Examples
@@ -186,7 +463,7 @@ impl Text {
     /// let text = Text::new("the bók", Some("the bok"));
     /// assert_eq!(Cow::<Text>::Owned(Text::from("the bók")), text.simplified());
     /// ```
-    pub fn simplified(&self) -> Cow<Self> {
+    pub fn simplified(&self) -> Cow<'_, Self> {
         match self.simplified_version() {
             Some(simple) => Cow::Owned(simple),
             None => Cow::Borrowed(self),
@@ -220,20 +497,108 @@ impl Text {
         let calc = calculate_ascii(&self.value)?;
 
         if ovr == &calc {
-            Some(Text::new(
-                self.value.clone(),
-                None as Option<Cow<'static, str>>,
-            ))
+            Some(
+                Text::new(self.value.clone(), None as Option<Cow<'static, str>>)
+                    .with_sort(self.sort.clone())
+                    .with_lock(self.locked),
+            )
         } else {
             None
         }
     }
 }
 
+/// Builds a `Text` with any of its representations overridden.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::Text;
+/// let text = Text::builder("本: 上")
+///     .with_ascii("hon: jou")
+///     .with_file_safe("hon (jou)")
+///     .with_sort("hon")
+///     .build();
+/// assert_eq!("hon: jou", text.ascii());
+/// assert_eq!("hon (jou)", text.file_safe());
+/// assert_eq!(Some("hon"), text.sort_override());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextBuilder {
+    value: Cow<'static, str>,
+    ascii: Option<Cow<'static, str>>,
+    file_safe: Option<Cow<'static, str>>,
+    sort: Option<Cow<'static, str>>,
+    locked: bool,
+    variants: BTreeMap<String, String>,
+}
+
+impl TextBuilder {
+    /// Override the ASCII representation.
+    pub fn with_ascii<T: Into<Cow<'static, str>>>(mut self, ascii: T) -> Self {
+        self.ascii = Some(ascii.into());
+        self
+    }
+
+    /// Override the filename safe representation. It's still made ASCII and file safe.
+    pub fn with_file_safe<T: Into<Cow<'static, str>>>(mut self, file_safe: T) -> Self {
+        self.file_safe = Some(file_safe.into());
+        self
+    }
+
+    /// Override the name the text is sorted by.
+    pub fn with_sort<T: Into<Cow<'static, str>>>(mut self, sort: T) -> Self {
+        self.sort = Some(sort.into());
+        self
+    }
+
+    /// Lock the text, so it's never changed by normalization.
+    pub fn with_lock(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    /// Add a version of the text in another language.
+    pub fn with_variant<L: Into<String>, T: Into<String>>(mut self, language: L, value: T) -> Self {
+        self.variants.insert(language.into(), value.into());
+        self
+    }
+
+    pub fn build(self) -> Text {
+        let mut text = Text::new(self.value, self.ascii)
+            .with_sort(self.sort)
+            .with_lock(self.locked);
+        text.variants = self.variants;
+
+        if let Some(file_safe) = self.file_safe {
+            let file_safe = calculate_ascii(&file_safe).unwrap_or_else(|| file_safe.into_owned());
+            let file_safe = make_file_safe(&file_safe).unwrap_or(file_safe);
+            text.file_safe = (file_safe != text.ascii()).then_some(file_safe);
+        }
+
+        text
+    }
+}
+
 impl Default for Text {
+    /// Returns the empty text.
     fn default() -> Self {
-        // TODO: Implement.
-        unimplemented!()
+        EMPTY_TEXT
+    }
+}
+
+impl fmt::Display for Text {
+    /// Writes the text's regular value.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl FromStr for Text {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Text::from(s.to_string()))
     }
 }
 
@@ -283,6 +648,11 @@ impl Add<Text> for Text {
             return self;
         }
 
+        let sort = add_sorts(&self, &other);
+        let variants = add_variants(&self, &other);
+
+        let locked = self.locked || other.locked;
+
         let file_safe = match (self.file_safe, other.file_safe) {
             (None, None) => None,
             (Some(mut a), None) => {
@@ -311,6 +681,9 @@ impl Add<Text> for Text {
             value,
             ascii,
             file_safe,
+            sort,
+            locked,
+            variants,
         }
     }
 }
@@ -323,6 +696,11 @@ impl Add<&Text> for Text {
             return self;
         }
 
+        let sort = add_sorts(&self, other);
+        let variants = add_variants(&self, other);
+
+        let locked = self.locked || other.locked;
+
         let file_safe = if let Some(mut a) = self.file_safe {
             a.push_str(other.file_safe());
             Some(a)
@@ -343,6 +721,9 @@ impl Add<&Text> for Text {
             value,
             ascii,
             file_safe,
+            sort,
+            locked,
+            variants,
         }
     }
 }
@@ -355,6 +736,11 @@ impl Add<Text> for &Text {
             return other;
         }
 
+        let sort = add_sorts(self, &other);
+        let variants = add_variants(self, &other);
+
+        let locked = self.locked || other.locked;
+
         let file_safe = if let Some(mut b) = other.file_safe {
             b.insert_str(0, self.file_safe());
             Some(b)
@@ -374,11 +760,14 @@ impl Add<Text> for &Text {
             value,
             ascii,
             file_safe,
+            sort,
+            locked,
+            variants,
         }
     }
 }
 
-impl<'a, 'b> Add<&'a Text> for &'b Text {
+impl<'a> Add<&'a Text> for &Text {
     type Output = Text;
 
     fn add(self, other: &'a Text) -> Self::Output {
@@ -398,21 +787,45 @@ impl<'a, 'b> Add<&'a Text> for &'b Text {
             value,
             ascii,
             file_safe,
+            sort: add_sorts(self, other),
+            locked: self.locked || other.locked,
+            variants: add_variants(self, other),
         }
     }
 }
 
 impl AddAssign<Text> for Text {
     fn add_assign(&mut self, other: Text) {
-        // TODO: Actually implement this correctly.
-        *self = &*self + other;
+        if *self == EMPTY_TEXT {
+            *self = other;
+        } else {
+            *self += &other;
+        }
     }
 }
 
 impl AddAssign<&Text> for Text {
+    /// Appends to the text in place, reusing its allocations.
     fn add_assign(&mut self, other: &Text) {
-        // TODO: Actually implement this correctly.
-        *self = &*self + other;
+        if *other == EMPTY_TEXT {
+            return;
+        } else if *self == EMPTY_TEXT {
+            *self = other.clone();
+            return;
+        }
+
+        self.sort = add_sorts(self, other);
+        self.variants = add_variants(self, other);
+        self.locked |= other.locked;
+
+        match (&mut self.file_safe, &other.file_safe) {
+            (Some(a), _) => a.push_str(other.file_safe()),
+            (None, Some(b)) => self.file_safe = Some(format!("{}{}", self.ascii(), b)),
+            (None, None) => {}
+        }
+
+        self.ascii.push(&self.value, &other.ascii, &other.value);
+        self.value.to_mut().push_str(&other.value);
     }
 }
 
@@ -421,20 +834,46 @@ impl Serialize for Text {
     where
         S: ser::Serializer,
     {
-        use ser::SerializeStruct;
+        use ser::SerializeMap;
 
-        match &self.ascii {
+        let ascii = match &self.ascii {
             Ascii::Different {
                 value,
                 is_overridden: true,
-            } => {
-                let mut state = serializer.serialize_struct("Text", 2)?;
-                state.serialize_field("text", &self.value)?;
-                state.serialize_field("ascii", &value)?;
-                state.end()
-            }
-            _ => serializer.serialize_str(&self.value),
+            } => Some(value),
+            _ => None,
+        };
+
+        let file_safe = self.file_safe_override();
+
+        if self.is_plain() {
+            return serializer.serialize_str(&self.value);
+        }
+
+        let num_fields = 1
+            + ascii.is_some() as usize
+            + file_safe.is_some() as usize
+            + self.sort.is_some() as usize
+            + self.locked as usize
+            + self.variants.len();
+        let mut state = serializer.serialize_map(Some(num_fields))?;
+        state.serialize_entry("text", &self.value)?;
+        if let Some(ascii) = ascii {
+            state.serialize_entry("ascii", ascii)?;
+        }
+        if let Some(file_safe) = file_safe {
+            state.serialize_entry("file-safe", file_safe)?;
+        }
+        if let Some(sort) = &self.sort {
+            state.serialize_entry("sort", sort)?;
         }
+        if self.locked {
+            state.serialize_entry("lock", &self.locked)?;
+        }
+        for (language, value) in &self.variants {
+            state.serialize_entry(language, value)?;
+        }
+        state.end()
     }
 }
 
@@ -476,20 +915,62 @@ impl<'de> Deserialize<'de> for Text {
                 enum Fields {
                     Text,
                     Ascii,
+                    #[serde(rename = "file-safe")]
+                    FileSafe,
+                    Sort,
+                    Lock,
+                    Variant(String),
                 }
 
                 let mut text: Option<String> = None;
                 let mut ascii: Option<String> = None;
+                let mut file_safe: Option<String> = None;
+                let mut sort: Option<String> = None;
+                let mut lock: Option<bool> = None;
+                let mut variants = BTreeMap::new();
 
                 while let Some(key) = map.next_key()? {
                     match key {
                         Fields::Text => field!(map, text),
                         Fields::Ascii => field!(map, ascii),
+                        Fields::FileSafe => field!(map, file_safe),
+                        Fields::Sort => field!(map, sort),
+                        Fields::Lock => field!(map, lock),
+                        Fields::Variant(language) => {
+                            if !is_language_code(&language) {
+                                return Err(de::Error::custom(format_args!(
+                                    "unknown field `{}`, expected text, ascii, file-safe, sort, \
+                                     lock or a language code",
+                                    language
+                                )));
+                            }
+                            if variants.contains_key(&language) {
+                                return Err(de::Error::custom(format_args!(
+                                    "duplicate variant `{}`",
+                                    language
+                                )));
+                            }
+                            let value: String = map.next_value()?;
+                            variants.insert(language, value);
+                        }
                     }
                 }
 
                 let text = text.ok_or_else(|| de::Error::missing_field("text"))?;
-                Ok(Text::new(text, ascii))
+                let mut builder = Text::builder(text).with_lock(lock.unwrap_or(false));
+                if let Some(ascii) = ascii {
+                    builder = builder.with_ascii(ascii);
+                }
+                if let Some(file_safe) = file_safe {
+                    builder = builder.with_file_safe(file_safe);
+                }
+                if let Some(sort) = sort {
+                    builder = builder.with_sort(sort);
+                }
+                for (language, value) in variants {
+                    builder = builder.with_variant(language, value);
+                }
+                Ok(builder.build())
             }
         }
 
@@ -497,6 +978,36 @@ impl<'de> Deserialize<'de> for Text {
     }
 }
 
+/// Adds the sort names of two texts, if either is overridden.
+fn add_sorts(left: &Text, right: &Text) -> Option<Cow<'static, str>> {
+    if left.sort.is_none() && right.sort.is_none() {
+        return None;
+    }
+    Some(format!("{}{}", left.sort_or_value(), right.sort_or_value()).into())
+}
+
+/// Adds the language variants of two texts, using a text's value where it has no variant.
+fn add_variants(left: &Text, right: &Text) -> BTreeMap<String, String> {
+    left.variants
+        .keys()
+        .chain(right.variants.keys())
+        .map(|language| {
+            let l = left.variant(language).unwrap_or(&left.value);
+            let r = right.variant(language).unwrap_or(&right.value);
+            (language.clone(), format!("{}{}", l, r))
+        })
+        .collect()
+}
+
+/// Returns if a map key is a language code, like "en" or "pt-BR", starting with an ISO 639
+/// language so typos like "srt" aren't taken for one.
+fn is_language_code(key: &str) -> bool {
+    let mut parts = key.split('-');
+    let language = parts.next().unwrap_or_default();
+    (language::is_iso_639_1(language) || language::is_iso_639_2(language))
+        && parts.all(|p| (2..=8).contains(&p.len()) && p.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
 /// Adds two cows together, reusing allocations if possible.
 fn add_cows<'a>(left: Cow<'a, str>, right: Cow<'a, str>) -> String {
     if let Cow::Owned(mut left) = left {
@@ -511,25 +1022,11 @@ fn add_cows<'a>(left: Cow<'a, str>, right: Cow<'a, str>) -> String {
 }
 
 fn calculate_ascii(s: &str) -> Option<String> {
-    use unicode_normalization::UnicodeNormalization;
-
     if s.is_ascii() {
         return None;
     }
 
-    fn char_ascii(c: char) -> Option<char> {
-        if c.is_ascii() {
-            Some(c)
-        } else if c == '“' || c == '”' {
-            Some('"')
-        } else if c == '‘' || c == '’' {
-            Some('\'')
-        } else {
-            None
-        }
-    }
-
-    s.nfkd().filter_map(char_ascii).collect::<String>().into()
+    Some(crate::translit::to_ascii(s))
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -599,6 +1096,30 @@ impl Ascii {
         }
     }
 
+    /// Appends a borrowed `Ascii` in place.
+    fn push(&mut self, left: &str, other: &Ascii, right: &str) {
+        if let (Self::Same, Self::Same) = (&*self, other) {
+            return;
+        }
+
+        let is_overridden = self.is_overridden() || other.is_overridden();
+        match self {
+            Self::Same => {
+                *self = Self::Different {
+                    value: format!("{}{}", left, other.for_value(right)).into(),
+                    is_overridden,
+                }
+            }
+            Self::Different {
+                value,
+                is_overridden: overridden,
+            } => {
+                value.to_mut().push_str(other.for_value(right));
+                *overridden = is_overridden;
+            }
+        }
+    }
+
     /// Adds an owned Ascii to an owned Ascii.
     fn add_owned_to_owned(self, left: &str, other: Ascii, right: &str) -> Ascii {
         if self == Self::Same && other == Self::Same {
@@ -742,7 +1263,21 @@ mod tests {
                 _ => unreachable!(),
             };
 
-            Text::new(value, ascii)
+            let mut builder = Text::builder(value).with_lock(bool::arbitrary(g));
+            if let Some(ascii) = ascii {
+                builder = builder.with_ascii(ascii);
+            }
+            if u8::arbitrary(g) % 4 == 0 {
+                builder = builder.with_file_safe(String::arbitrary(g));
+            }
+            if u8::arbitrary(g) % 4 == 0 {
+                builder = builder.with_sort(String::arbitrary(g));
+            }
+            if u8::arbitrary(g) % 4 == 0 {
+                let language = *g.choose(&["en", "ja", "pt-BR"]).unwrap();
+                builder = builder.with_variant(language, String::arbitrary(g));
+            }
+            builder.build()
         }
 
         // TODO: Implement a better shrinking strategy.
@@ -760,6 +1295,12 @@ mod tests {
         fn translates_full_width_chars() {
             assert_eq!(calculate_ascii("Ｆｏｏ！"), Some(String::from("Foo!")));
         }
+
+        #[test]
+        fn transliterates_non_latin_scripts() {
+            assert_eq!(calculate_ascii("Кино"), Some(String::from("Kino")));
+            assert_eq!(calculate_ascii("ひかり"), Some(String::from("hikari")));
+        }
     }
 
     mod value {
@@ -946,6 +1487,94 @@ mod tests {
         }
     }
 
+    mod add_assign {
+        use super::*;
+
+        #[quickcheck]
+        fn owned_is_same_as_add(a: Text, b: Text) -> bool {
+            let mut c = a.clone();
+            c += b.clone();
+            c == a + b
+        }
+
+        #[quickcheck]
+        fn ref_is_same_as_add(a: Text, b: Text) -> bool {
+            let mut c = a.clone();
+            c += &b;
+            c == a + b
+        }
+
+        #[test]
+        fn reuses_owned_value() {
+            let mut text = Text::from(String::from("foo"));
+            text.value.to_mut().reserve(16);
+            let ptr = text.value().as_ptr();
+            text += &Text::from("bar");
+            assert_eq!("foobar", text.value());
+            assert_eq!(ptr, text.value().as_ptr());
+        }
+    }
+
+    mod join {
+        use super::*;
+
+        #[quickcheck]
+        fn is_same_as_adding(texts: Vec<Text>, separator: Text) -> bool {
+            // Built with `Add`, which `join` should match.
+            let expected = texts
+                .iter()
+                .enumerate()
+                .fold(EMPTY_TEXT, |acc, (i, text)| match i {
+                    0 => acc + text,
+                    _ => acc + &separator + text,
+                });
+            Text::join(&texts, &separator) == expected
+        }
+    }
+
+    mod builder {
+        use super::*;
+
+        #[test]
+        fn file_safe_is_made_safe() {
+            let text = Text::builder("a").with_file_safe("ö: b").build();
+            assert_eq!("o - b", text.file_safe());
+        }
+
+        #[test]
+        fn file_safe_same_as_ascii_isnt_stored() {
+            let text = Text::builder("a: b").with_file_safe("a - b").build();
+            assert_eq!(Text::from("a: b"), text);
+        }
+
+        #[quickcheck]
+        fn default_is_empty(a: Text) -> bool {
+            Text::default() + &a == a && &a + Text::default() == a
+        }
+
+        #[quickcheck]
+        fn display_and_from_str_use_value(a: String) -> bool {
+            let text: Text = a.parse().unwrap();
+            text.to_string() == a && text == Text::from(a)
+        }
+    }
+
+    mod variants {
+        use super::*;
+
+        #[test]
+        fn are_added_using_values_where_missing() {
+            let a = Text::from("本").with_variant("en", "Book");
+            let b = Text::from(" 2");
+            assert_eq!(Some("Book 2"), (a + b).variant("en"));
+        }
+
+        #[quickcheck]
+        fn in_missing_variant_is_same(a: Text) -> bool {
+            a.in_variant(Some("de")) == Cow::Borrowed(&a)
+        }
+    }
+
     mod simplified {
         use super::*;
 
@@ -994,6 +1623,25 @@ mod tests {
                 .unwrap();
             assert_eq!(text, new_text);
         }
+
+        #[test]
+        fn sort_text_is_serde_equal() {
+            let text = Text::from("The The").with_sort(Some("The The"));
+            let new_text: Text = serde_yaml::to_string(&text)
+                .and_then(|s| serde_yaml::from_str(&s))
+                .unwrap();
+            assert_eq!(text, new_text);
+        }
+    }
+
+    mod round_trip {
+        use super::*;
+
+        #[quickcheck]
+        fn yaml_is_same(a: Text) -> bool {
+            let yaml = serde_yaml::to_string(&a).unwrap();
+            serde_yaml::from_str::<Text>(&yaml).unwrap() == a
+        }
     }
 
     mod ser {
@@ -1053,6 +1701,30 @@ mod tests {
             assert_eq!(Text::new("foo", Some("bar")), text);
         }
 
+        #[test]
+        fn yaml_with_sort_parses_sort() {
+            let text: Text = serde_yaml::from_str("{text: The The, sort: The The}").unwrap();
+            assert_eq!(Some("The The"), text.sort_override());
+            assert_eq!("The The", text.sortable_file_safe());
+        }
+
+        #[test]
+        fn yaml_with_language_keys_parses_variants() {
+            let text: Text = serde_yaml::from_str("{text: 本, ascii: hon, en: Book}").unwrap();
+            assert_eq!("hon", text.ascii());
+            assert_eq!(vec![("en", "Book")], text.variants().collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn yaml_with_unknown_key_doesnt_parse() {
+            let text = serde_yaml::from_str::<Text>("{text: foo, Ascii: bar}");
+            assert!(text.is_err());
+            let text = serde_yaml::from_str::<Text>("{text: foo, srt: bar}");
+            assert!(text.is_err());
+            let text = serde_yaml::from_str::<Text>("{text: foo, pt-BR: bar, deu: baz}");
+            assert_eq!(2, text.unwrap().variants().count());
+        }
+
         #[test]
         fn yaml_non_string_or_hash_doesnt_parse() {
             let text = serde_yaml::from_str::<Text>("[]");
@@ -1,8 +1,21 @@
 use super::{album::Album, disc::Disc};
 use crate::{
+    artists::ArtistStyle,
+    artwork::{Artwork, ArtworkKind},
+    carry::Inherited,
+    comment::Comment,
+    covers::CoverSource,
+    credits::Credits,
+    custom::{Custom, CustomFrame},
+    date::Date,
+    file_safety::FileSafety,
     image::{self as img, Image, LoadWithCacheError},
+    lyrics::{self, Lyrics, LyricsError},
+    profile::ExportFormat,
     raw,
+    release::Release,
     utils::{comma_separated, num_digits},
+    work::Movement,
     Text,
 };
 use anyhow::{Context, Result as AnyhowResult};
@@ -34,21 +47,31 @@ impl<'a> Track<'a> {
         }
     }
 
+    pub fn raw(&self) -> &raw::Track {
+        self.track
+    }
+
     pub fn title(&self) -> &Text {
         &self.track.title
     }
 
+    /// Gets the title in the language variant of an export format's profile.
+    pub fn title_for(&self, format: ExportFormat) -> Cow<'_, Text> {
+        self.album().profile(format).text(self.title())
+    }
+
     pub fn artists(&self) -> &[Text] {
         self.track
             .artists()
+            .or_else(|| {
+                let position = self.inherited().artists?;
+                self.album().raw_track(position).artists()
+            })
             .unwrap_or_else(|| self.album().artists())
     }
 
-    pub fn artist(&self) -> Cow<Text> {
-        self.track
-            .artists()
-            .map(comma_separated)
-            .unwrap_or_else(|| self.album().artist())
+    pub fn artist(&self) -> Cow<'_, Text> {
+        self.album().artist_style().join.join(self.artists())
     }
 
     pub fn album_artists(&self) -> Option<&[Text]> {
@@ -60,70 +83,157 @@ impl<'a> Track<'a> {
         }
     }
 
-    pub fn album_artist(&self) -> Option<Cow<Text>> {
-        self.album_artists().map(comma_separated)
+    pub fn album_artist(&self) -> Option<Cow<'_, Text>> {
+        let join = self.album().artist_style().join;
+        self.album_artists().map(|artists| join.join(artists))
+    }
+
+    /// Gets the track's release date, from the track, its disc, an earlier track or its album.
+    pub fn date(&self) -> Option<Date> {
+        self.track.date.or_else(|| self.inherited().date)
+    }
+
+    /// Gets the date the track was first released, from the track, its disc or its album.
+    pub fn original_date(&self) -> Option<Date> {
+        self.track
+            .original_date
+            .or_else(|| self.disc.original_date())
+            .or_else(|| self.album().original_date())
     }
 
     pub fn year(&self) -> Option<usize> {
-        if let Some(year) = self.track.year {
-            return Some(year);
-        }
+        self.date().map(|date| date.year().into())
+    }
 
-        let album_year = self.album().year()?;
-        if let raw::AlbumYear::Year(year) = album_year {
-            return Some(year);
-        }
+    pub fn genre(&self) -> Option<&Text> {
+        self.track
+            .genre()
+            .or_else(|| {
+                let position = self.inherited().genre?;
+                self.album().raw_track(position).genre()
+            })
+            .or_else(|| self.album().genre())
+    }
 
-        if self.track_number > 1 {
-            return self.disc.track(self.track_number - 1)?.year();
-        }
+    pub fn comments(&self) -> &[Comment] {
+        self.track
+            .comments()
+            .or_else(|| {
+                let position = self.inherited().comment?;
+                self.album().raw_track(position).comments()
+            })
+            .unwrap_or_default()
+    }
 
-        if self.disc.disc_number > 1 {
-            let disc = self.album().disc(self.disc.disc_number - 1)?;
-            return disc.track(disc.num_tracks())?.year();
-        }
+    /// Gets the work the track is a movement of, if it is one.
+    pub fn movement(&self) -> Option<&Movement> {
+        self.track.movement()
+    }
 
-        None
+    /// Gets the people credited on the track, with its disc's and album's filling in any roles
+    /// it doesn't set.
+    pub fn credits(&self) -> Credits {
+        self.track.credits().inherit(&self.disc().credits())
     }
 
-    pub fn genre(&self) -> Option<&Text> {
-        self.track.genre().or_else(|| self.album().genre())
+    /// Gets the track's custom fields, with its disc's and album's that it doesn't set or
+    /// remove.
+    pub fn custom(&self) -> Custom {
+        self.track.custom().inherit(&self.disc().custom())
     }
 
-    pub fn comment(&self) -> Option<&Text> {
-        self.track.comment()
+    /// Gets what the track inherits from earlier tracks, its disc and its album.
+    fn inherited(&self) -> Inherited {
+        self.album()
+            .inherited(self.disc().disc_number, self.track_number)
     }
 
     pub fn lyrics(&self) -> Option<&Text> {
         self.track.lyrics()
     }
 
+    /// Gets the language the track is sung in, from the track or album, or English if neither
+    /// sets it.
+    pub fn language(&self) -> &str {
+        self.track
+            .language()
+            .or_else(|| self.album().language())
+            .unwrap_or(lyrics::DEFAULT_LANGUAGE)
+    }
+
+    /// Gets the path of the track's lyrics file in the lyrics folder.
+    pub fn lyrics_file(&self) -> Option<PathBuf> {
+        lyrics::find_with_name(&self.album().lyrics_path(), &self.lyrics_name())
+    }
+
+    /// Gets the name of the track's lyrics file without its extension: its canonical filename,
+    /// like "01 - Yesterday", with its disc number first if the album has several, like
+    /// "2-01 - Yesterday".
+    fn lyrics_name(&self) -> String {
+        let filename = self.canonical_filename();
+        let stem = filename.strip_suffix(".mp3").unwrap_or(&filename);
+        let num_discs = self.album().num_discs();
+        if num_discs == 1 {
+            stem.to_string()
+        } else {
+            let digits = num_digits(num_discs);
+            format!(
+                "{:0width$}-{}",
+                self.disc().disc_number,
+                stem,
+                width = digits
+            )
+        }
+    }
+
+    /// Loads the track's lyrics, from the album definition or else its lyrics file.
+    pub fn load_lyrics(&self) -> Result<Option<Lyrics>, LyricsError> {
+        if let Some(lyrics) = self.lyrics() {
+            return Ok(Some(Lyrics::plain(lyrics.value())));
+        }
+        self.lyrics_file()
+            .map(|path| Lyrics::load(&path))
+            .transpose()
+    }
+
     pub fn album(&self) -> &Album {
         self.disc().album
     }
 
-    pub fn disc(&self) -> &Disc {
+    pub fn disc(&self) -> &Disc<'_> {
         &self.disc
     }
 
     pub fn canonical_filename(&self) -> String {
+        self.canonical_filename_for(self.album().file_safety())
+    }
+
+    /// Gets the canonical filename, made safe with the given rules.
+    pub fn canonical_filename_for(&self, safety: FileSafety) -> String {
+        self.numbered_filename(ExportFormat::Full, safety)
+    }
+
+    /// Gets the filename within a disc, using the title variant of the given format.
+    fn numbered_filename(&self, format: ExportFormat, safety: FileSafety) -> String {
+        let title = self.file_title(format, safety);
+
         // If this is a single disc, single track album, don't print the track number.
         let num_tracks = self.disc().num_tracks();
         let num_discs = self.album().num_discs();
         if num_tracks == 1 && num_discs == 1 {
-            format!("{}.mp3", self.title().file_safe())
+            safety.file_name(&title, "mp3")
         } else {
             let digits = num_digits(num_tracks);
-            format!(
-                "{:0width$} - {}.mp3",
-                self.track_number,
-                self.title().file_safe(),
-                width = digits,
-            )
+            let stem = format!("{:0width$} - {}", self.track_number, title, width = digits,);
+            safety.file_name(&stem, "mp3")
         }
     }
 
-    pub fn filename(&self) -> Cow<str> {
+    fn file_title(&self, format: ExportFormat, safety: FileSafety) -> String {
+        file_title(self.album(), self.title(), self.movement(), format, safety)
+    }
+
+    pub fn filename(&self) -> Cow<'_, str> {
         match self.track.filename() {
             Some(filename) => filename.into(),
             None => self.canonical_filename().into(),
@@ -131,19 +241,25 @@ impl<'a> Track<'a> {
     }
 
     pub fn filename_vw(&self) -> String {
+        self.filename_vw_for(self.album().file_safety())
+    }
+
+    /// Gets the filename used in car exports, made safe with the given rules.
+    pub fn filename_vw_for(&self, safety: FileSafety) -> String {
         if self.album().num_discs() == 1 {
-            return self.canonical_filename();
+            return self.numbered_filename(ExportFormat::Vw, safety);
         }
         let disc_digits = num_digits(self.album().num_discs());
         let track_digits = num_digits(self.disc().num_tracks());
-        format!(
-            "{:0disc_width$}-{:0track_width$} - {}.mp3",
+        let stem = format!(
+            "{:0disc_width$}-{:0track_width$} - {}",
             self.disc().disc_number,
             self.track_number,
-            self.title().file_safe(),
+            self.file_title(ExportFormat::Vw, safety),
             disc_width = disc_digits,
             track_width = track_digits,
-        )
+        );
+        safety.file_name(&stem, "mp3")
     }
 
     pub fn canonical_path(&self) -> PathBuf {
@@ -161,26 +277,41 @@ impl<'a> Track<'a> {
         self.path().exists()
     }
 
-    fn get_cover<'b, P, F, G>(
+    fn get_cover<'b, G>(
         &'b self,
         cover: &'b OnceCell<Option<Image>>,
-        covers_path: P,
-        transform: F,
+        format: ExportFormat,
         fallback: G,
     ) -> Result<Option<&'b Image>, LoadWithCacheError>
     where
-        P: AsRef<Path>,
-        F: Fn(image::DynamicImage) -> Result<Image, image::ImageError>,
         G: Fn() -> Result<Option<&'b Image>, LoadWithCacheError>,
     {
         cover
             .get_or_try_init(|| {
-                Image::try_load_with_cache(
-                    self.album().image_path(),
-                    covers_path,
-                    self.track.title.file_safe(),
-                    transform,
-                )
+                let album = self.album();
+                let settings = album.profile(format).cover;
+                let covers_path = album.covers_path_for(&settings);
+                let transform = |raw| img::transform_cover(raw, &settings);
+
+                match self.track.cover() {
+                    Some(file) => Image::load_file_with_cache(
+                        album.image_path().join(file),
+                        covers_path,
+                        &self.cover_cache_name(&img::file_stem(file)),
+                        transform,
+                    )
+                    .map(Some),
+                    None => {
+                        let name = self.title().file_safe();
+                        Image::try_load_with_cache_as(
+                            album.image_path(),
+                            covers_path,
+                            name,
+                            &self.cover_cache_name(name),
+                            transform,
+                        )
+                    }
+                }
             })
             .and_then(|o| match o {
                 Some(x) => Ok(Some(x)),
@@ -188,22 +319,41 @@ impl<'a> Track<'a> {
             })
     }
 
-    pub fn cover(&self) -> Result<Option<&Image>, LoadWithCacheError> {
-        self.get_cover(
-            &self.cover,
-            self.album().covers_path(),
-            img::transform_image,
-            || self.disc().cover(),
+    /// Gets the path of the track's own source cover, without falling back to the disc's.
+    pub fn cover_file(&self) -> Option<PathBuf> {
+        let images = self.album().image_path();
+        match self.track.cover() {
+            Some(file) => Some(images.join(file)),
+            None => img::find_with_name(&images, self.title().file_safe()),
+        }
+    }
+
+    /// Gets where the track's cover comes from.
+    pub fn cover_source(&self) -> Option<CoverSource> {
+        self.cover_file()
+            .map(CoverSource::Track)
+            .or_else(|| self.disc().cover_source())
+    }
+
+    /// Gets the name the track's cover is cached under.
+    ///
+    /// The name includes the disc and track numbers, so tracks with the same title never share
+    /// a cached cover.
+    fn cover_cache_name(&self, source: &str) -> String {
+        format!(
+            "Disc {} Track {} - {}",
+            self.disc().disc_number,
+            self.track_number,
+            source
         )
     }
 
+    pub fn cover(&self) -> Result<Option<&Image>, LoadWithCacheError> {
+        self.get_cover(&self.cover, ExportFormat::Full, || self.disc().cover())
+    }
+
     pub fn cover_vw(&self) -> Result<Option<&Image>, LoadWithCacheError> {
-        self.get_cover(
-            &self.cover_vw,
-            self.album().covers_vw_path(),
-            img::transform_image_vw,
-            || self.disc().cover_vw(),
-        )
+        self.get_cover(&self.cover_vw, ExportFormat::Vw, || self.disc().cover_vw())
     }
 
     pub fn validate(&self) -> Result<(), Vec<ValidateError>> {
@@ -223,7 +373,7 @@ impl<'a> Track<'a> {
         push_err! {
             match tag.title() {
                 None => Some(ValidateError::MissingFrame("title")),
-                Some(title) if title != self.title().value() => {
+                Some(title) if title != self.title_for(ExportFormat::Full).value() => {
                     Some(ValidateError::IncorrectDataInFrame("title", title.to_string()))
                 }
                 _ => None,
@@ -233,7 +383,7 @@ impl<'a> Track<'a> {
         push_err! {
             match (
                 !self.artists().is_empty(),
-                self.artist().value(),
+                self.artist_style().frame_value(self.artists()),
                 tag.artist(),
             ) {
                 (false, _, Some(_)) => Some(ValidateError::UnexpectedFrame("artist")),
@@ -245,21 +395,23 @@ impl<'a> Track<'a> {
             }
         }
 
+        let expected_track = self.id3_track(ExportFormat::Full);
         push_err! {
             match tag.track() {
                 None => Some(ValidateError::MissingFrame("track")),
-                Some(track) if track != self.track_number as u32 => {
-                    Some(ValidateError::IncorrectDataInFrame("track", track.to_string()))
+                Some(track) if (track, tag.total_tracks()) != expected_track => {
+                    let found = position_value(track, tag.total_tracks());
+                    Some(ValidateError::IncorrectDataInFrame("track", found))
                 }
                 _ => None,
             }
         }
 
         push_err! {
-            match (self.album_artist(), tag.album_artist()) {
+            match (self.album_artist_frame(), tag.album_artist()) {
                 (Some(_), None) => Some(ValidateError::MissingFrame("album artist")),
                 (None, Some(_)) => Some(ValidateError::UnexpectedFrame("album artist")),
-                (Some(ref a), Some(b)) if a.value() != b => {
+                (Some(ref a), Some(b)) if a != b => {
                     Some(ValidateError::IncorrectDataInFrame("album artist", b.to_string()))
                 }
                 _ => None,
@@ -267,15 +419,12 @@ impl<'a> Track<'a> {
         }
 
         push_err! {
-            match (
-                !self.disc().is_only_disc(),
-                self.disc().disc_number as u32,
-                tag.disc(),
-            ) {
-                (false, _, Some(_)) => Some(ValidateError::UnexpectedFrame("disc")),
-                (true, _, None) => Some(ValidateError::MissingFrame("disc")),
-                (true, disc, Some(t_disc)) if disc != t_disc => {
-                    Some(ValidateError::IncorrectDataInFrame("disc", t_disc.to_string()))
+            match (self.id3_disc(ExportFormat::Full), tag.disc()) {
+                (None, Some(_)) => Some(ValidateError::UnexpectedFrame("disc")),
+                (Some(_), None) => Some(ValidateError::MissingFrame("disc")),
+                (Some(disc), Some(t_disc)) if disc != (t_disc, tag.total_discs()) => {
+                    let found = position_value(t_disc, tag.total_discs());
+                    Some(ValidateError::IncorrectDataInFrame("disc", found))
                 }
                 _ => None,
             }
@@ -284,22 +433,66 @@ impl<'a> Track<'a> {
         push_err! {
             match tag.album() {
                 None => Some(ValidateError::MissingFrame("album")),
-                Some(album) if album != self.album().title().value() => {
+                Some(album) if album != self.album().title_for(ExportFormat::Full).value() => {
                     Some(ValidateError::IncorrectDataInFrame("album", album.to_string()))
                 }
                 _ => None,
             }
         }
 
-        push_err! {
-            match (self.id3_date_recorded(), tag.date_recorded()) {
-                (None, Some(_)) => Some(ValidateError::UnexpectedFrame("year")),
-                (Some(_), None) => Some(ValidateError::MissingFrame("year")),
-                // TODO: Does comparing date_recordeds work?
-                (Some(a), Some(b)) if a != b => {
-                    Some(ValidateError::IncorrectDataInFrame("year", b.to_string()))
+        for (id, name, text) in self.id3_text_frames() {
+            let existing = tag.get(id).and_then(|f| f.content().text());
+            push_err!(check_frame(name, text.as_deref(), existing));
+        }
+
+        for (description, name, text) in self.id3_extended_texts() {
+            let existing = tag
+                .extended_texts()
+                .find(|t| t.description == description)
+                .map(|t| t.value.as_str());
+            push_err!(check_frame(name, text.as_deref(), existing));
+        }
+
+        // Other TXXX and WXXX frames aren't reported, since rippers add their own. Frames of
+        // removed fields are dropped when `update_id3` rewrites the tag.
+        for (name, frame) in self.custom().frames() {
+            let (existing, expected) = match frame {
+                CustomFrame::Text(value) => (
+                    tag.extended_texts()
+                        .find(|t| t.description == name)
+                        .map(|t| t.value.as_str()),
+                    value,
+                ),
+                CustomFrame::Link(link) => (
+                    tag.extended_links()
+                        .find(|l| l.description == name)
+                        .map(|l| l.link.as_str()),
+                    link,
+                ),
+            };
+            match existing {
+                None => errors.push(ValidateError::MissingCustomField(name.to_string())),
+                Some(existing) if existing != expected => errors.push(
+                    ValidateError::IncorrectCustomField(name.to_string(), existing.to_string()),
+                ),
+                _ => {}
+            }
+        }
+
+        for (id, name, date) in self.id3_dates() {
+            let existing = tag.get(id).and_then(|f| f.content().text());
+            let existing_date = existing
+                .and_then(|t| t.parse().ok())
+                .and_then(|t| Date::from_timestamp(&t));
+            push_err! {
+                match (date, existing) {
+                    (None, Some(_)) => Some(ValidateError::UnexpectedFrame(name)),
+                    (Some(_), None) => Some(ValidateError::MissingFrame(name)),
+                    (Some(a), Some(b)) if Some(a) != existing_date => {
+                        Some(ValidateError::IncorrectDataInFrame(name, b.to_string()))
+                    }
+                    _ => None,
                 }
-                _ => None,
             }
         }
 
@@ -314,43 +507,100 @@ impl<'a> Track<'a> {
             }
         }
 
-        push_err! {
-            match (self.id3_comment(), tag.comments().next()) {
-                (None, Some(_)) => Some(ValidateError::UnexpectedFrame("comments")),
-                (Some(_), None) => Some(ValidateError::MissingFrame("comments")),
-                // TODO: Does comparing comments work?
-                (Some(ref a), Some(b)) if a != b => {
-                    Some(ValidateError::IncorrectDataInFrame("comments", format!("{:?}", b)))
+        match self.id3_comments() {
+            Err(e) => errors.push(e),
+            // Comments are compared as a set, since their order in the tag doesn't matter.
+            Ok(comments) => {
+                let existing = tag.comments().collect::<Vec<_>>();
+                let key = |c: &id3::frame::Comment| (c.lang.clone(), c.description.clone());
+                for comment in &comments {
+                    match existing.iter().find(|c| key(c) == key(comment)) {
+                        None => errors.push(ValidateError::MissingFrame("comment")),
+                        Some(c) if *c != comment => errors.push(
+                            ValidateError::IncorrectDataInFrame("comment", c.text.clone()),
+                        ),
+                        _ => {}
+                    }
+                }
+                if existing
+                    .iter()
+                    .any(|c| !comments.iter().any(|comment| key(c) == key(comment)))
+                {
+                    errors.push(ValidateError::UnexpectedFrame("comment"));
                 }
-                _ => None,
             }
         }
 
-        push_err! {
-            match (self.id3_lyrics(), tag.lyrics().next()) {
-                (None, Some(_)) => Some(ValidateError::UnexpectedFrame("lyrics")),
-                (Some(_), None) => Some(ValidateError::MissingFrame("lyrics")),
-                // TODO: Does comparing lyrics work?
-                (Some(ref a), Some(b)) if a != b => {
-                    Some(ValidateError::IncorrectDataInFrame("lyrics", format!("{:?}", b)))
+        match self.id3_lyrics() {
+            Ok((lyrics, synced)) => {
+                push_err! {
+                    match (lyrics, tag.lyrics().next()) {
+                        (None, Some(_)) => Some(ValidateError::UnexpectedFrame("lyrics")),
+                        (Some(_), None) => Some(ValidateError::MissingFrame("lyrics")),
+                        (Some(ref a), Some(b)) if a != b => {
+                            Some(ValidateError::IncorrectDataInFrame("lyrics", format!("{:?}", b)))
+                        }
+                        _ => None,
+                    }
+                }
+
+                push_err! {
+                    match (synced, tag.synchronised_lyrics().next()) {
+                        (None, Some(_)) => Some(ValidateError::UnexpectedFrame("synced lyrics")),
+                        (Some(_), None) => Some(ValidateError::MissingFrame("synced lyrics")),
+                        (Some(ref a), Some(b)) if a != b => Some(
+                            ValidateError::IncorrectDataInFrame("synced lyrics", b.lang.clone()),
+                        ),
+                        _ => None,
+                    }
                 }
-                _ => None,
             }
+            Err(e) => errors.push(ValidateError::CouldntLoadLyrics(e)),
         }
 
-        push_err! {
-            match self.cover_id3_picture() {
-                Ok(cover) => match (cover, tag.pictures().next()) {
-                    (None, Some(_)) => Some(ValidateError::UnexpectedFrame("cover")),
-                    (Some(_), None) => Some(ValidateError::MissingFrame("cover")),
-                    // TODO: Does comparing pictures work?
-                    (Some(ref a), Some(b)) if a != b => {
-                        Some(ValidateError::IncorrectDataInFrame("cover", String::from("...")))
+        for (id, name, sort) in self.id3_sort_names() {
+            let existing = tag.get(id).and_then(|f| f.content().text());
+            push_err!(check_frame(name, sort.as_deref(), existing));
+        }
+
+        match self.id3_pictures(ExportFormat::Full) {
+            Ok(mut pictures) => {
+                // The tag reader only keeps the last picture of each type, so booklet pages
+                // before the last one can't be checked.
+                let mut i = 0;
+                while i < pictures.len() {
+                    let picture_type = pictures[i].picture_type;
+                    if pictures[i + 1..]
+                        .iter()
+                        .any(|p| p.picture_type == picture_type)
+                    {
+                        pictures.remove(i);
+                    } else {
+                        i += 1;
                     }
-                    _ => None,
-                },
-                Err(err) => Some(ValidateError::CouldntLoadCover(err)),
+                }
+
+                let key = |p: &id3::frame::Picture| (p.picture_type, p.description.clone());
+                let existing = tag.pictures().collect::<Vec<_>>();
+
+                for picture in &pictures {
+                    match existing.iter().find(|p| key(p) == key(picture)) {
+                        None => errors.push(ValidateError::MissingPicture(picture_name(picture))),
+                        // TODO: Does comparing pictures work?
+                        Some(p) if *p != picture => errors.push(
+                            ValidateError::IncorrectDataInFrame("picture", picture_name(picture)),
+                        ),
+                        _ => {}
+                    }
+                }
+
+                for picture in existing {
+                    if !pictures.iter().any(|p| key(p) == key(picture)) {
+                        errors.push(ValidateError::UnexpectedPicture(picture_name(picture)));
+                    }
+                }
             }
+            Err(err) => errors.push(ValidateError::CouldntLoadCover(err)),
         }
 
         // TODO: Check for duplicate and erroneous frames.
@@ -373,45 +623,86 @@ impl<'a> Track<'a> {
     fn tag(&self) -> AnyhowResult<Tag> {
         let mut tag = Tag::new();
 
-        tag.set_title(self.title().value());
+        tag.set_title(self.title_for(ExportFormat::Full).value());
 
         if !self.artists().is_empty() {
-            tag.set_artist(self.artist().value());
+            tag.set_artist(self.artist_style().frame_value(self.artists()));
         }
 
-        tag.set_track(self.track_number as u32);
+        set_track(&mut tag, self.id3_track(ExportFormat::Full));
 
-        if let Some(album_artist) = self.album_artist() {
-            tag.set_album_artist(album_artist.value());
+        if let Some(album_artist) = self.album_artist_frame() {
+            tag.set_album_artist(album_artist);
         }
 
-        if !self.disc().is_only_disc() {
-            tag.set_disc(self.disc().disc_number as u32);
+        if let Some(disc) = self.id3_disc(ExportFormat::Full) {
+            set_disc(&mut tag, disc);
         }
 
-        tag.set_album(self.album().title().value());
+        tag.set_album(self.album().title_for(ExportFormat::Full).value());
 
-        if let Some(date_recorded) = self.id3_date_recorded() {
-            tag.set_date_recorded(date_recorded);
+        for (id, _, date) in self.id3_dates() {
+            if let Some(date) = date {
+                tag.set_text(id, date.to_string());
+            }
         }
 
         if let Some(genre) = self.genre() {
             tag.set_genre(genre.value());
         }
 
-        if let Some(comment) = self.id3_comment() {
+        for (id, _, sort) in self.id3_sort_names() {
+            if let Some(sort) = sort {
+                tag.set_text(id, sort);
+            }
+        }
+
+        for (id, _, text) in self.id3_text_frames() {
+            if let Some(text) = text {
+                tag.set_text(id, text);
+            }
+        }
+
+        for (description, _, text) in self.id3_extended_texts() {
+            if let Some(text) = text {
+                tag.add_frame(extended_text_frame(description, text));
+            }
+        }
+
+        for (name, frame) in self.custom().frames() {
+            match frame {
+                CustomFrame::Text(value) => {
+                    tag.add_frame(extended_text_frame(name, value.to_string()));
+                }
+                CustomFrame::Link(link) => {
+                    tag.add_frame(id3::frame::ExtendedLink {
+                        description: name.to_string(),
+                        link: link.to_string(),
+                    });
+                }
+            }
+        }
+
+        let comments = self
+            .id3_comments()
+            .context("Comments need different descriptions or languages")?;
+        for comment in comments {
             tag.add_frame(comment);
-            // tag.add_comment(comment);
         }
 
-        if let Some(lyrics) = self.id3_lyrics() {
+        let (lyrics, synced) = self.id3_lyrics().context("Couldn't load lyrics")?;
+        if let Some(lyrics) = lyrics {
             tag.add_frame(lyrics);
         }
-
-        if let Some(picture) = self.cover_id3_picture().context("Couldn't load cover")? {
-            tag.add_frame(picture);
+        if let Some(synced) = synced {
+            tag.add_frame(synced);
         }
 
+        // Pictures are added directly, since `add_frame` would replace booklet pages that share
+        // a picture type.
+        let pictures = self.id3_pictures(ExportFormat::Full)?;
+        tag.extend(pictures.into_iter().map(Into::into));
+
         Ok(tag)
     }
 
@@ -434,7 +725,7 @@ impl<'a> Track<'a> {
             .with_context(|| format!("Couldn't write tag to {:?}", &path))
     }
 
-    pub fn export<P: Into<PathBuf>>(&self, folder: P) -> AnyhowResult<()> {
+    pub fn export<P: Into<PathBuf>>(&self, folder: P, safety: FileSafety) -> AnyhowResult<()> {
         let orig_path = self.path();
         let mut path: PathBuf = folder.into();
 
@@ -445,18 +736,18 @@ impl<'a> Track<'a> {
                 .with_context(|| format!("Couldn't create {:?}", &path))?;
         }
 
-        path.push(self.filename_vw());
+        path.push(self.filename_vw_for(safety));
         fs::copy(&orig_path, &path)
             .with_context(|| format!("Couldn't copy {:?} to {:?}", &orig_path, &path))
             .map(|_| ())
     }
 
-    pub fn update_id3_vw<P: AsRef<Path>>(&self, folder: P) -> AnyhowResult<()> {
+    pub fn update_id3_vw<P: AsRef<Path>>(&self, folder: P, safety: FileSafety) -> AnyhowResult<()> {
         let orig_path = self.path();
         let folder = folder.as_ref();
 
         // Copy file to destination.
-        let path = folder.join(&self.filename_vw() as &str);
+        let path = folder.join(self.filename_vw_for(safety));
         fs::copy(&orig_path, &path)
             .with_context(|| format!("Couldn't copy {:?} to {:?}", &orig_path, &path))?;
 
@@ -467,77 +758,384 @@ impl<'a> Track<'a> {
 
         let mut tag = Tag::new();
 
-        tag.set_title(self.title().ascii());
+        tag.set_title(self.title_for(ExportFormat::Vw).ascii());
 
+        if let Some(alternate) = self.id3_alternate_title(ExportFormat::Vw) {
+            tag.add_frame(extended_text_frame(
+                ALTERNATE_TITLE,
+                Text::from(alternate).ascii().to_string(),
+            ));
+        }
+
+        // Car stereos only read a single value, so the ASCII export keeps the comma form.
         if !self.artists().is_empty() {
-            tag.set_artist(self.artist().ascii());
+            tag.set_artist(comma_separated(self.artists()).ascii());
         }
 
-        tag.set_track(self.track_number as u32);
+        set_track(&mut tag, self.id3_track(ExportFormat::Vw));
 
-        if let Some(album_artist) = self.album_artist() {
-            tag.set_album_artist(album_artist.ascii());
+        if let Some(album_artists) = self.album_artists() {
+            tag.set_album_artist(comma_separated(album_artists).ascii());
         }
 
-        if !self.disc().is_only_disc() {
-            tag.set_disc(self.disc().disc_number as u32);
+        if let Some(disc) = self.id3_disc(ExportFormat::Vw) {
+            set_disc(&mut tag, disc);
         }
 
-        tag.set_album(self.album().title().ascii());
+        tag.set_album(self.album().title_for(ExportFormat::Vw).ascii());
 
-        if let Some(Image { data, format }) = self.cover_vw().context("Couldn't load cover")? {
-            let cover = id3::frame::Picture {
-                mime_type: format.mime().to_string(),
-                picture_type: id3::frame::PictureType::CoverFront,
-                description: "".to_string(),
-                data: data.clone(),
-            };
-            tag.add_frame(cover);
-        }
+        let pictures = self.id3_pictures(ExportFormat::Vw)?;
+        tag.extend(pictures.into_iter().map(Into::into));
 
         tag.write_to_path(&path, Version::Id3v24)
             .with_context(|| format!("Couldn't write tag to {:?}", path))
     }
 
-    fn id3_date_recorded(&self) -> Option<id3::Timestamp> {
-        self.year().map(|year| id3::Timestamp {
-            year: year as i32,
-            month: None,
-            day: None,
-            hour: None,
-            minute: None,
-            second: None,
-        })
+    fn artist_style(&self) -> ArtistStyle {
+        self.album().artist_style()
     }
 
-    fn id3_comment(&self) -> Option<id3::frame::Comment> {
-        self.comment().map(|comment| id3::frame::Comment {
-            lang: "eng".to_string(),
-            description: "".to_string(),
-            text: comment.value().to_string(),
-        })
+    /// Gets the contents of the album artist frame, if the album artists are overridden.
+    fn album_artist_frame(&self) -> Option<String> {
+        self.album_artists()
+            .map(|artists| self.artist_style().frame_value(artists))
+    }
+
+    /// Gets the frame ID, name and contents of the title, artist, album artist and album sort
+    /// frames. Sort names that match the name they're for aren't written, except a
+    /// compilation's album artist sort, so players that fall back to each track's artist sort
+    /// keep its tracks together under "Various Artists".
+    fn id3_sort_names(&self) -> [(&'static str, &'static str, Option<String>); 4] {
+        let album = self.album();
+        let articles = album.articles();
+        let sort = |text: &Text| {
+            let sort = text.sort_name_with(articles);
+            (sort != text.value()).then(|| sort.into_owned())
+        };
+
+        let artist = if self.artists().is_empty() {
+            None
+        } else {
+            sort(&self.artist())
+        };
+
+        [
+            (
+                "TSOT",
+                "title sort",
+                sort(&self.title_for(ExportFormat::Full)),
+            ),
+            ("TSOP", "artist sort", artist),
+            (
+                "TSO2",
+                "album artist sort",
+                self.album_artist().and_then(|a| {
+                    if album.is_compilation() {
+                        Some(a.sort_name_with(articles).into_owned())
+                    } else {
+                        sort(&a)
+                    }
+                }),
+            ),
+            (
+                "TSOA",
+                "album sort",
+                sort(&album.title_for(ExportFormat::Full)),
+            ),
+        ]
+    }
+
+    /// Gets the track number, and the number of tracks on the disc if the profile writes totals.
+    fn id3_track(&self, format: ExportFormat) -> (u32, Option<u32>) {
+        let total = self.album().profile(format).totals;
+        (
+            self.track_number as u32,
+            total.then(|| self.disc().num_tracks() as u32),
+        )
+    }
+
+    /// Gets the disc number, and the number of discs if the profile writes totals, or None if
+    /// the album only has one disc and the profile doesn't number single discs.
+    fn id3_disc(&self, format: ExportFormat) -> Option<(u32, Option<u32>)> {
+        let profile = self.album().profile(format);
+        if self.disc().is_only_disc() && !profile.single_disc {
+            return None;
+        }
+        Some((
+            self.disc().disc_number as u32,
+            profile.totals.then(|| self.album().num_discs() as u32),
+        ))
+    }
+
+    /// Gets the frame ID, name and contents of the work and movement frames.
+    ///
+    /// The work is written to TIT1, and to GRP1 where iTunes looks for it.
+    fn id3_work(&self) -> [(&'static str, &'static str, Option<String>); 4] {
+        let movement = self.movement();
+        let work = movement.map(|m| {
+            self.album()
+                .profile(ExportFormat::Full)
+                .text(&m.work)
+                .value()
+                .to_string()
+        });
+        [
+            ("TIT1", "work", work.clone()),
+            ("GRP1", "work grouping", work),
+            (
+                "MVNM",
+                "movement name",
+                movement.map(|_| self.title_for(ExportFormat::Full).value().to_string()),
+            ),
+            (
+                "MVIN",
+                "movement number",
+                movement.map(Movement::frame_value),
+            ),
+        ]
+    }
+
+    /// Gets the title in another language written to a TXXX frame, if the profile writes one.
+    fn id3_alternate_title(&self, format: ExportFormat) -> Option<String> {
+        self.album()
+            .profile(format)
+            .alternate_title(self.title())
+            .map(str::to_string)
+    }
+
+    /// Gets the frame ID, name and contents of the compilation, work, credit and release detail
+    /// text frames.
+    fn id3_text_frames(&self) -> Vec<(&'static str, &'static str, Option<String>)> {
+        let compilation = self.album().is_compilation().then(|| String::from("1"));
+        let mut frames = vec![("TCMP", "compilation", compilation)];
+        frames.extend(self.id3_work());
+        frames.extend(self.credits().frames(self.artist_style()));
+        frames.extend(
+            self.album()
+                .release()
+                .text_frames()
+                .iter()
+                .map(|&(id, name, text)| (id, name, text.map(str::to_string))),
+        );
+        frames
+    }
+
+    /// Gets the description, name and contents of the TXXX frames, which hold the alternate
+    /// title and the release details.
+    fn id3_extended_texts(&self) -> Vec<(&'static str, &'static str, Option<String>)> {
+        extended_texts(
+            self.id3_alternate_title(ExportFormat::Full),
+            self.album().release(),
+        )
+    }
+
+    /// Gets the frame ID, name and contents of the date frames.
+    ///
+    /// Tags are written as ID3v2.4, so the release date goes in TDRL and in TDRC, which most
+    /// players show as the year, and the original release date in TDOR.
+    fn id3_dates(&self) -> [(&'static str, &'static str, Option<Date>); 3] {
+        [
+            ("TDRC", "year", self.date()),
+            ("TDRL", "release date", self.date()),
+            ("TDOR", "original release date", self.original_date()),
+        ]
+    }
+
+    /// Gets a COMM frame for each comment, in the track's language if a comment doesn't have
+    /// its own.
+    ///
+    /// Comments that end up with the same language and description would replace each other in
+    /// the tag, so they're an error.
+    fn id3_comments(&self) -> Result<Vec<id3::frame::Comment>, ValidateError> {
+        let frames = self
+            .comments()
+            .iter()
+            .map(|comment| comment.frame(self.language()))
+            .collect::<Vec<_>>();
+        let key = |c: &id3::frame::Comment| (c.lang.clone(), c.description.clone());
+        for (i, frame) in frames.iter().enumerate() {
+            if frames[i + 1..].iter().any(|f| key(f) == key(frame)) {
+                return Err(ValidateError::DuplicateFrame("comment"));
+            }
+        }
+        Ok(frames)
     }
 
-    fn id3_lyrics(&self) -> Option<id3::frame::Lyrics> {
-        // TODO: Handle non-English lyrics.
-        self.lyrics().map(|lyrics| id3::frame::Lyrics {
-            lang: "eng".to_string(),
+    /// Gets the USLT frame with the track's lyrics, and the SYLT frame with their timings if
+    /// they're synchronized.
+    fn id3_lyrics(
+        &self,
+    ) -> Result<
+        (
+            Option<id3::frame::Lyrics>,
+            Option<id3::frame::SynchronisedLyrics>,
+        ),
+        LyricsError,
+    > {
+        use id3::frame::{SynchronisedLyrics, SynchronisedLyricsType, TimestampFormat};
+
+        let Some(lyrics) = self.load_lyrics()? else {
+            return Ok((None, None));
+        };
+        let lang = self.language().to_string();
+        let synced = lyrics.synced.map(|content| SynchronisedLyrics {
+            lang: lang.clone(),
+            timestamp_format: TimestampFormat::Ms,
+            content_type: SynchronisedLyricsType::Lyrics,
             description: "".to_string(),
-            text: lyrics.value().to_string(),
-        })
+            content,
+        });
+        let lyrics = id3::frame::Lyrics {
+            lang,
+            description: "".to_string(),
+            text: lyrics.text,
+        };
+        Ok((Some(lyrics), synced))
     }
 
-    fn cover_id3_picture(&self) -> AnyhowResult<Option<id3::frame::Picture>> {
-        let frame = self
-            .cover()
-            .context("Couldn't load cover")?
-            .map(|img| id3::frame::Picture {
-                mime_type: img.format.mime().to_string(),
-                picture_type: id3::frame::PictureType::CoverFront,
-                description: "".to_string(),
-                data: img.data.clone(),
-            });
-        Ok(frame)
+    fn id3_pictures(&self, format: ExportFormat) -> AnyhowResult<Vec<id3::frame::Picture>> {
+        let mut pictures = Vec::new();
+
+        if self
+            .album()
+            .profile(format)
+            .includes(ArtworkKind::FrontCover)
+        {
+            let cover = match format {
+                ExportFormat::Full => self.cover(),
+                ExportFormat::Vw => self.cover_vw(),
+            };
+
+            if let Some(img) = cover.context("Couldn't load cover")? {
+                pictures.push(id3::frame::Picture {
+                    mime_type: img.format.mime().to_string(),
+                    picture_type: id3::frame::PictureType::CoverFront,
+                    description: "".to_string(),
+                    data: img.data.clone(),
+                });
+            }
+        }
+
+        let artwork = match format {
+            ExportFormat::Full => self.album().artwork(),
+            ExportFormat::Vw => self.album().artwork_vw(),
+        };
+        let disc_number = self.disc().disc_number;
+        pictures.extend(
+            artwork
+                .context("Couldn't load artwork")?
+                .iter()
+                .filter(|a| a.is_for_disc(disc_number))
+                .map(Artwork::id3_picture),
+        );
+
+        Ok(pictures)
+    }
+}
+
+/// The description of the TXXX frame holding a title in another language.
+const ALTERNATE_TITLE: &str = "ALTERNATE TITLE";
+
+/// Gets the part of a filename naming a track, like "Symphony 9 - II. Molto vivace" for a
+/// movement.
+fn file_title(
+    album: &Album,
+    title: &Text,
+    movement: Option<&Movement>,
+    format: ExportFormat,
+    safety: FileSafety,
+) -> String {
+    let profile = album.profile(format);
+    let title = profile.text(title);
+    let title = title.file_safe_source(safety);
+    match movement {
+        None => title.to_string(),
+        Some(movement) => {
+            let work = profile.text(&movement.work);
+            format!(
+                "{} - {}. {}",
+                work.file_safe_source(safety),
+                movement.numeral(),
+                title
+            )
+        }
+    }
+}
+
+/// Gets the description, name and contents of every TXXX frame maestro writes itself.
+fn extended_texts(
+    alternate_title: Option<String>,
+    release: &Release,
+) -> Vec<(&'static str, &'static str, Option<String>)> {
+    let mut texts = vec![(ALTERNATE_TITLE, "alternate title", alternate_title)];
+    texts.extend(
+        release
+            .extended_texts()
+            .iter()
+            .map(|&(description, name, text)| (description, name, text.map(str::to_string))),
+    );
+    texts
+}
+
+/// Returns if maestro writes a TXXX frame with a description itself, so it can't be used for
+/// anything else.
+pub(crate) fn is_reserved_description(description: &str) -> bool {
+    extended_texts(None, &Release::default())
+        .iter()
+        .any(|(reserved, _, _)| reserved.eq_ignore_ascii_case(description))
+}
+
+fn extended_text_frame(description: &str, value: String) -> id3::frame::ExtendedText {
+    id3::frame::ExtendedText {
+        description: description.to_string(),
+        value,
+    }
+}
+
+fn set_track(tag: &mut Tag, (track, total): (u32, Option<u32>)) {
+    tag.set_track(track);
+    if let Some(total) = total {
+        tag.set_total_tracks(total);
+    }
+}
+
+fn set_disc(tag: &mut Tag, (disc, total): (u32, Option<u32>)) {
+    tag.set_disc(disc);
+    if let Some(total) = total {
+        tag.set_total_discs(total);
+    }
+}
+
+/// Writes a track or disc number the way it's stored in the tag, like "3/12".
+fn position_value(number: u32, total: Option<u32>) -> String {
+    match total {
+        Some(total) => format!("{}/{}", number, total),
+        None => number.to_string(),
+    }
+}
+
+/// Compares the expected contents of a frame with what's in the tag.
+fn check_frame(
+    name: &'static str,
+    expected: Option<&str>,
+    existing: Option<&str>,
+) -> Option<ValidateError> {
+    match (expected, existing) {
+        (None, Some(_)) => Some(ValidateError::UnexpectedFrame(name)),
+        (Some(_), None) => Some(ValidateError::MissingFrame(name)),
+        (Some(a), Some(b)) if a != b => {
+            Some(ValidateError::IncorrectDataInFrame(name, b.to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// Gets a name for a picture to use in errors.
+fn picture_name(picture: &id3::frame::Picture) -> String {
+    if picture.description.is_empty() {
+        picture.picture_type.to_string()
+    } else {
+        picture.description.clone()
     }
 }
 
@@ -558,8 +1156,23 @@ pub enum ValidateError {
     #[error("unexpected frame {0}")]
     UnexpectedFrame(&'static str),
 
+    #[error("missing picture {0}")]
+    MissingPicture(String),
+
+    #[error("unexpected picture {0}")]
+    UnexpectedPicture(String),
+
     #[error("couldn't load cover")]
     CouldntLoadCover(#[from] anyhow::Error),
+
+    #[error("missing custom field {0}")]
+    MissingCustomField(String),
+
+    #[error("incorrect data in custom field {0}")]
+    IncorrectCustomField(String, String),
+
+    #[error("couldn't load lyrics")]
+    CouldntLoadLyrics(#[source] LyricsError),
 }
 
 pub struct TrackMut<'a> {
@@ -585,17 +1198,23 @@ impl<'a> TrackMut<'a> {
         &self.track.title
     }
 
+    /// Gets the title in the language variant of an export format's profile.
+    pub fn title_for(&self, format: ExportFormat) -> Cow<'_, Text> {
+        self.album().profile(format).text(self.title())
+    }
+
     pub fn artists(&self) -> &[Text] {
         self.track
             .artists()
+            .or_else(|| {
+                let position = self.inherited().artists?;
+                self.album().raw_track(position).artists()
+            })
             .unwrap_or_else(|| self.album().artists())
     }
 
-    pub fn artist(&self) -> Cow<Text> {
-        self.track
-            .artists()
-            .map(comma_separated)
-            .unwrap_or_else(|| self.album().artist())
+    pub fn artist(&self) -> Cow<'_, Text> {
+        self.album().artist_style().join.join(self.artists())
     }
 
     pub fn album_artists(&self) -> Option<&[Text]> {
@@ -607,38 +1226,63 @@ impl<'a> TrackMut<'a> {
         }
     }
 
-    pub fn album_artist(&self) -> Option<Cow<Text>> {
-        self.album_artists().map(comma_separated)
+    pub fn album_artist(&self) -> Option<Cow<'_, Text>> {
+        let join = self.album().artist_style().join;
+        self.album_artists().map(|artists| join.join(artists))
     }
 
-    pub fn year(&self) -> Option<usize> {
-        if let Some(year) = self.track.year {
-            return Some(year);
-        }
+    /// Gets the track's release date, from the track, its disc, an earlier track or its album.
+    pub fn date(&self) -> Option<Date> {
+        self.track.date.or_else(|| self.inherited().date)
+    }
 
-        let album_year = self.album().year()?;
-        if let raw::AlbumYear::Year(year) = album_year {
-            return Some(year);
-        }
+    /// Gets the date the track was first released, from the track, its disc or its album.
+    pub fn original_date(&self) -> Option<Date> {
+        self.track
+            .original_date
+            .or_else(|| self.disc.original_date())
+            .or_else(|| self.album().original_date())
+    }
 
-        if self.track_number > 1 {
-            return self.disc.track(self.track_number - 1)?.year();
-        }
+    pub fn year(&self) -> Option<usize> {
+        self.date().map(|date| date.year().into())
+    }
 
-        if self.disc.disc_number > 1 {
-            let disc = self.album().disc(self.disc.disc_number - 1)?;
-            return disc.track(disc.num_tracks())?.year();
-        }
+    pub fn genre(&self) -> Option<&Text> {
+        self.track
+            .genre()
+            .or_else(|| {
+                let position = self.inherited().genre?;
+                self.album().raw_track(position).genre()
+            })
+            .or_else(|| self.album().genre())
+    }
 
-        None
+    pub fn comments(&self) -> &[Comment] {
+        self.track
+            .comments()
+            .or_else(|| {
+                let position = self.inherited().comment?;
+                self.album().raw_track(position).comments()
+            })
+            .unwrap_or_default()
     }
 
-    pub fn genre(&self) -> Option<&Text> {
-        self.track.genre().or_else(|| self.album().genre())
+    /// Gets the work the track is a movement of, if it is one.
+    pub fn movement(&self) -> Option<&Movement> {
+        self.track.movement()
     }
 
-    pub fn comment(&self) -> Option<&Text> {
-        self.track.comment()
+    /// Gets the people credited on the track, with its disc's and album's filling in any roles
+    /// it doesn't set.
+    pub fn credits(&self) -> Credits {
+        self.track.credits().inherit(&self.disc().credits())
+    }
+
+    /// Gets what the track inherits from earlier tracks, its disc and its album.
+    fn inherited(&self) -> Inherited {
+        self.album()
+            .inherited(self.disc().disc_number, self.track_number)
     }
 
     pub fn lyrics(&self) -> Option<&Text> {
@@ -649,21 +1293,27 @@ impl<'a> TrackMut<'a> {
         self.disc().album
     }
 
-    pub fn disc(&self) -> &Disc {
+    pub fn disc(&self) -> &Disc<'_> {
         &self.disc
     }
 
+    fn file_title(&self, format: ExportFormat) -> String {
+        let safety = self.album().file_safety();
+        file_title(self.album(), self.title(), self.movement(), format, safety)
+    }
+
     pub fn canonical_filename(&self) -> String {
         let digits = num_digits(self.disc().num_tracks());
-        format!(
-            "{:0width$} - {}.mp3",
+        let stem = format!(
+            "{:0width$} - {}",
             self.track_number,
-            self.title().file_safe(),
+            self.file_title(ExportFormat::Full),
             width = digits,
-        )
+        );
+        self.album().file_safety().file_name(&stem, "mp3")
     }
 
-    pub fn filename(&self) -> Cow<str> {
+    pub fn filename(&self) -> Cow<'_, str> {
         match self.track.filename() {
             Some(filename) => filename.into(),
             None => self.canonical_filename().into(),
@@ -676,14 +1326,15 @@ impl<'a> TrackMut<'a> {
         }
         let disc_digits = num_digits(self.album().num_discs());
         let track_digits = num_digits(self.disc().num_tracks());
-        format!(
-            "{:0disc_width$}-{:0track_width$} - {}.mp3",
+        let stem = format!(
+            "{:0disc_width$}-{:0track_width$} - {}",
             self.disc().disc_number,
             self.track_number,
-            self.title().file_safe(),
+            self.file_title(ExportFormat::Vw),
             disc_width = disc_digits,
             track_width = track_digits,
-        )
+        );
+        self.album().file_safety().file_name(&stem, "mp3")
     }
 
     pub fn canonical_path(&self) -> PathBuf {
@@ -819,17 +1470,381 @@ mod tests {
         assert_eq!("song.mp3", filename);
     }
 
+    #[test]
+    fn filename_follows_album_file_safety() {
+        let album = raw::Album::new("foo").with_discs(vec![raw::Disc::from_tracks(vec![
+            raw::Track::new("What? [Live]"),
+            raw::Track::new("other"),
+        ])]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        assert_eq!("1 - What [Live].mp3", track.canonical_filename());
+        assert_eq!(
+            "1 - What? [Live].mp3",
+            track.canonical_filename_for(FileSafety::Posix)
+        );
+
+        let album = album.with_file_safety(FileSafety::Fat32);
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        assert_eq!("1 - What (Live).mp3", track.canonical_filename());
+    }
+
+    #[test]
+    fn vw_profile_uses_title_variant() {
+        let profiles = serde_yaml::from_str("vw: {variant: en, alternate-title: true}").unwrap();
+        let album = raw::Album::new(Text::from(("本", "hon")).with_variant("en", "Book"))
+            .with_profiles(profiles)
+            .with_discs(vec![raw::Disc::from_tracks(vec![
+                raw::Track::new(Text::from(("上", "jou")).with_variant("en", "Up")),
+                raw::Track::new("other"),
+            ])]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        assert_eq!("1 - jou.mp3", track.canonical_filename());
+        assert_eq!("1 - Up.mp3", track.filename_vw());
+        assert_eq!("Book", album.title_for(ExportFormat::Vw).value());
+        assert_eq!(
+            Some(String::from("上")),
+            track.id3_alternate_title(ExportFormat::Vw)
+        );
+        assert_eq!(None, track.id3_alternate_title(ExportFormat::Full));
+    }
+
+    #[test]
+    fn totals_follow_profile() {
+        let album = |profiles: &str| {
+            let album = raw::Album::new("foo")
+                .with_profiles(serde_yaml::from_str(profiles).unwrap())
+                .with_discs(vec![raw::Disc::from_tracks(vec![
+                    raw::Track::new("a"),
+                    raw::Track::new("b"),
+                ])]);
+            Album::new(album, PathBuf::from("."))
+        };
+
+        let default = album("{}");
+        let track = default.disc(1).unwrap().into_track(2).unwrap();
+        assert_eq!((2, Some(2)), track.id3_track(ExportFormat::Full));
+        assert_eq!(None, track.id3_disc(ExportFormat::Full));
+
+        let single = album("{full: {single-disc: true}, vw: {totals: false}}");
+        let track = single.disc(1).unwrap().into_track(1).unwrap();
+        assert_eq!(Some((1, Some(1))), track.id3_disc(ExportFormat::Full));
+        assert_eq!((1, None), track.id3_track(ExportFormat::Vw));
+        assert_eq!(None, track.id3_disc(ExportFormat::Vw));
+    }
+
+    #[test]
+    fn lyrics_are_loaded_from_files() {
+        let folder = tempfile::tempdir().unwrap();
+        let lyrics = folder.path().join("extras").join("lyrics");
+        fs::create_dir_all(&lyrics).unwrap();
+        fs::write(lyrics.join("1-1 - a.lrc"), "[00:01.00]Eins\n[00:02.00]Zwei").unwrap();
+        fs::write(lyrics.join("1-2 - b.txt"), "File").unwrap();
+        fs::write(lyrics.join("2-1 - a.txt"), "Other").unwrap();
+
+        let album = raw::Album::new("foo")
+            .with_language(String::from("deu"))
+            .with_discs(vec![
+                raw::Disc::from_tracks(vec![
+                    raw::Track::new("a"),
+                    raw::Track::new("b")
+                        .with_lyrics("Inline")
+                        .with_language(String::from("eng")),
+                ]),
+                raw::Disc::from_tracks(vec![raw::Track::new("a")]),
+            ]);
+        let album = Album::new(album, folder.path().to_path_buf());
+        let disc = album.disc(1).unwrap();
+
+        let (plain, synced) = disc.track(1).unwrap().id3_lyrics().unwrap();
+        assert_eq!("Eins\nZwei", plain.unwrap().text);
+        let synced = synced.unwrap();
+        assert_eq!("deu", synced.lang);
+        assert_eq!(
+            vec![(1000, "Eins".into()), (2000, "Zwei".into())],
+            synced.content
+        );
+
+        let (plain, synced) = disc.track(2).unwrap().id3_lyrics().unwrap();
+        let plain = plain.unwrap();
+        assert_eq!(
+            ("Inline", "eng"),
+            (plain.text.as_str(), plain.lang.as_str())
+        );
+        assert_eq!(None, synced);
+
+        let track = album.disc(2).unwrap().into_track(1).unwrap();
+        let (plain, synced) = track.id3_lyrics().unwrap();
+        assert_eq!("Other", plain.unwrap().text);
+        assert_eq!(None, synced);
+    }
+
+    #[test]
+    fn comments_are_written_in_their_language() {
+        let album = raw::Album::new("foo")
+            .with_language(String::from("deu"))
+            .with_discs(vec![raw::Disc::from_tracks(vec![raw::Track::new("a")
+                .with_comments(vec![
+                    Comment::new("Live"),
+                    Comment::new("Vinyl")
+                        .with_description(String::from("source"))
+                        .with_language(String::from("eng")),
+                ])])]);
+        let album = Album::new(album, PathBuf::from("."));
+        let track = album.disc(1).unwrap().into_track(1).unwrap();
+        let frames = track
+            .id3_comments()
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.lang, c.description, c.text))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("deu".into(), "".into(), "Live".into()),
+                ("eng".into(), "source".into(), "Vinyl".into()),
+            ],
+            frames
+        );
+    }
+
+    #[test]
+    fn comments_in_the_default_language_are_duplicates() {
+        let album = |comments: Vec<Comment>| {
+            let album = raw::Album::new("foo").with_discs(vec![raw::Disc::from_tracks(vec![
+                raw::Track::new("a").with_comments(comments),
+            ])]);
+            Album::new(album, PathBuf::from("."))
+        };
+
+        let implicit = album(vec![
+            Comment::new("a"),
+            Comment::new("b").with_language(String::from("eng")),
+        ]);
+        let track = implicit.disc(1).unwrap().into_track(1).unwrap();
+        assert!(matches!(
+            track.id3_comments(),
+            Err(ValidateError::DuplicateFrame("comment"))
+        ));
+        assert!(track.tag().is_err());
+
+        let described = album(vec![
+            Comment::new("a"),
+            Comment::new("b").with_description(String::from("x")),
+        ]);
+        let track = described.disc(1).unwrap().into_track(1).unwrap();
+        assert_eq!(2, track.id3_comments().unwrap().len());
+    }
+
+    #[test]
+    fn custom_fields_are_inherited() {
+        let album = serde_yaml::from_str::<raw::Album>(
+            "
+            title: foo
+            artist: a
+            custom:
+                source: CD
+                shop: https://example.com/foo
+            discs:
+                - custom: {shop: ~}
+                  tracks:
+                    - a
+                    - title: b
+                      custom: {source: vinyl rip}
+                - [c]
+            ",
+        )
+        .unwrap();
+        let album = Album::new(album, PathBuf::from("."));
+
+        let custom = album.disc(1).unwrap().into_track(2).unwrap().custom();
+        assert_eq!(
+            vec![("source", CustomFrame::Text("vinyl rip"))],
+            custom.frames().collect::<Vec<_>>()
+        );
+        let custom = album.disc(2).unwrap().into_track(1).unwrap().custom();
+        assert_eq!(
+            vec![
+                ("shop", CustomFrame::Link("https://example.com/foo")),
+                ("source", CustomFrame::Text("CD")),
+            ],
+            custom.frames().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn removed_custom_fields_are_dropped_by_update() {
+        let folder = tempfile::tempdir().unwrap();
+        let album = raw::Album::new("foo")
+            .with_artists(vec![Text::from("a")])
+            .with_discs(vec![raw::Disc::from_tracks(vec![raw::Track::new("a")
+                .with_filename(Some(String::from("a.mp3")))
+                .with_custom(
+                    Custom::default().with("source", String::from("CD")),
+                )])]);
+        let album = Album::new(album, folder.path().to_path_buf());
+        let track = album.disc(1).unwrap().into_track(1).unwrap();
+        fs::write(track.path(), b"").unwrap();
+
+        let mut tag = track.tag().unwrap();
+        for description in ["shop", "MusicBrainz Album Id"] {
+            tag.add_frame(id3::frame::ExtendedText {
+                description: description.to_string(),
+                value: String::from("x"),
+            });
+        }
+        tag.write_to_path(track.path(), Version::Id3v24).unwrap();
+        assert!(track.validate().is_ok());
+
+        track.update_id3(false).unwrap();
+        let tag = Tag::read_from_path(track.path()).unwrap();
+        let descriptions = tag
+            .extended_texts()
+            .map(|t| t.description.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["source"], descriptions);
+    }
+
+    #[test]
+    fn movements_are_named_after_their_work() {
+        let disc = serde_yaml::from_str::<raw::Disc>(
+            "
+            - work:
+                text: Symphony No. 9 in D minor, Op. 125
+                file-safe: Symphony 9
+              movements:
+                - Allegro ma non troppo
+                - Molto vivace
+            - Encore
+            ",
+        )
+        .unwrap();
+        let album = raw::Album::new("foo").with_discs(vec![disc]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(2).unwrap();
+        assert_eq!(
+            "2 - Symphony 9 - II. Molto vivace.mp3",
+            track.canonical_filename()
+        );
+        assert_eq!(
+            "3 - Encore.mp3",
+            disc.track(3).unwrap().canonical_filename()
+        );
+
+        let mut raw = track.raw().clone();
+        let track_mut = TrackMut::new(Cow::Borrowed(&disc), &mut raw, 2);
+        assert_eq!(track.canonical_filename(), track_mut.canonical_filename());
+        assert_eq!(track.filename_vw(), track_mut.filename_vw());
+
+        let work = String::from("Symphony No. 9 in D minor, Op. 125");
+        assert_eq!(
+            [
+                ("TIT1", "work", Some(work.clone())),
+                ("GRP1", "work grouping", Some(work)),
+                ("MVNM", "movement name", Some(String::from("Molto vivace"))),
+                ("MVIN", "movement number", Some(String::from("2/2"))),
+            ],
+            track.id3_work()
+        );
+        assert!(disc
+            .track(3)
+            .unwrap()
+            .id3_work()
+            .iter()
+            .all(|f| f.2.is_none()));
+    }
+
+    #[test]
+    fn credits_are_inherited_by_role() {
+        let credits = |yaml| serde_yaml::from_str::<crate::credits::Credits>(yaml).unwrap();
+        let album = raw::Album::new("foo")
+            .with_credits(credits("{composer: Beethoven, conductor: Karajan}"))
+            .with_discs(vec![raw::Disc::from_tracks(vec![
+                raw::Track::new("a"),
+                raw::Track::new("b").with_credits(credits("{composer: Schubert, remixers: []}")),
+            ])
+            .with_credits(credits("{lyricist: Schiller, remixer: Someone}"))]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+
+        let frames = |track: Track| {
+            track
+                .id3_text_frames()
+                .into_iter()
+                .filter_map(|(id, _, text)| Some((id, text?)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            vec![
+                ("TCOM", String::from("Beethoven")),
+                ("TEXT", String::from("Schiller")),
+                ("TPE3", String::from("Karajan")),
+                ("TPE4", String::from("Someone")),
+            ],
+            frames(disc.track(1).unwrap())
+        );
+        assert_eq!(
+            vec![
+                ("TCOM", String::from("Schubert")),
+                ("TEXT", String::from("Schiller")),
+                ("TPE3", String::from("Karajan")),
+            ],
+            frames(disc.track(2).unwrap())
+        );
+    }
+
+    #[test]
+    fn release_details_are_written_to_frames() {
+        use crate::release::{Release, ReleaseType};
+
+        let album = raw::Album::new("foo")
+            .with_release(Release {
+                label: Some(Text::from("Parlophone")),
+                catalog_number: Some(String::from("CDNODATA 02")),
+                release_type: Some(ReleaseType::Album),
+                ..Default::default()
+            })
+            .with_discs(vec![raw::Disc::from_tracks(vec![raw::Track::new("a")])]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+
+        let texts = track
+            .id3_text_frames()
+            .into_iter()
+            .filter_map(|(id, _, text)| Some((id, text?)))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![("TPUB", String::from("Parlophone"))], texts);
+
+        let extended = track
+            .id3_extended_texts()
+            .into_iter()
+            .filter_map(|(description, _, text)| Some((description, text?)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("CATALOGNUMBER", String::from("CDNODATA 02")),
+                ("RELEASETYPE", String::from("album")),
+            ],
+            extended
+        );
+    }
+
     #[test]
     fn track_has_no_year() {
         let album = raw::Album::new("foo")
             .with_artists(vec![Text::from("a"), Text::from(("b", "c"))])
             .with_discs(vec![
                 raw::Disc::from_tracks(vec![
-                    raw::Track::new("song a 1").with_year(2020),
+                    raw::Track::new("song a 1").with_date(Date::from_year(2020)),
                     raw::Track::new("song a 2"),
                 ]),
                 raw::Disc::from_tracks(vec![
-                    raw::Track::new("song b 1").with_year(2021),
+                    raw::Track::new("song b 1").with_date(Date::from_year(2021)),
                     raw::Track::new("song b 2"),
                 ]),
             ]);
@@ -846,11 +1861,11 @@ mod tests {
             .with_artists(vec![Text::from("a"), Text::from(("b", "c"))])
             .with_discs(vec![
                 raw::Disc::from_tracks(vec![
-                    raw::Track::new("song a 1").with_year(2020),
+                    raw::Track::new("song a 1").with_date(Date::from_year(2020)),
                     raw::Track::new("song a 2"),
                 ]),
                 raw::Disc::from_tracks(vec![
-                    raw::Track::new("song b 1").with_year(2021),
+                    raw::Track::new("song b 1").with_date(Date::from_year(2021)),
                     raw::Track::new("song b 2"),
                 ]),
             ]);
@@ -865,15 +1880,15 @@ mod tests {
     fn track_has_inherited_year() {
         let album = raw::Album::new("foo")
             .with_artists(vec![Text::from("a"), Text::from(("b", "c"))])
-            .with_year(raw::AlbumYear::Year(2022))
+            .with_date(raw::AlbumDate::Date(Date::from_year(2022)))
             .with_discs(vec![
                 raw::Disc::from_tracks(vec![
-                    raw::Track::new("song a 1").with_year(2020),
+                    raw::Track::new("song a 1").with_date(Date::from_year(2020)),
                     raw::Track::new("song a 2"),
                 ]),
                 raw::Disc::from_tracks(vec![
                     raw::Track::new("song b 1"),
-                    raw::Track::new("song b 2").with_year(2021),
+                    raw::Track::new("song b 2").with_date(Date::from_year(2021)),
                     raw::Track::new("song b 3"),
                 ]),
             ]);
@@ -888,15 +1903,15 @@ mod tests {
     fn track_has_carried_year_from_previous_track_in_same_disc() {
         let album = raw::Album::new("foo")
             .with_artists(vec![Text::from("a"), Text::from(("b", "c"))])
-            .with_year(raw::AlbumYear::Carry)
+            .with_date(raw::AlbumDate::Carry)
             .with_discs(vec![
                 raw::Disc::from_tracks(vec![
-                    raw::Track::new("song a 1").with_year(2020),
+                    raw::Track::new("song a 1").with_date(Date::from_year(2020)),
                     raw::Track::new("song a 2"),
                 ]),
                 raw::Disc::from_tracks(vec![
                     raw::Track::new("song b 1"),
-                    raw::Track::new("song b 2").with_year(2021),
+                    raw::Track::new("song b 2").with_date(Date::from_year(2021)),
                     raw::Track::new("song b 3"),
                 ]),
             ]);
@@ -911,15 +1926,15 @@ mod tests {
     fn track_has_carried_year_from_last_track_in_previous_disc() {
         let album = raw::Album::new("foo")
             .with_artists(vec![Text::from("a"), Text::from(("b", "c"))])
-            .with_year(raw::AlbumYear::Carry)
+            .with_date(raw::AlbumDate::Carry)
             .with_discs(vec![
                 raw::Disc::from_tracks(vec![
-                    raw::Track::new("song a 1").with_year(2020),
+                    raw::Track::new("song a 1").with_date(Date::from_year(2020)),
                     raw::Track::new("song a 2"),
                 ]),
                 raw::Disc::from_tracks(vec![
                     raw::Track::new("song b 1"),
-                    raw::Track::new("song b 2").with_year(2021),
+                    raw::Track::new("song b 2").with_date(Date::from_year(2021)),
                     raw::Track::new("song b 3"),
                 ]),
             ]);
@@ -929,4 +1944,216 @@ mod tests {
         let track = disc.track(1).unwrap();
         assert_eq!(Some(2020), track.year());
     }
+
+    #[test]
+    fn carried_fields_come_from_previous_track() {
+        use crate::carry::Carried;
+
+        let album = raw::Album::new("foo")
+            .with_artists(vec![Text::from("Various")])
+            .with_genre(Text::from("Rock"))
+            .with_carry(vec![Carried::Artists, Carried::Genre])
+            .with_discs(vec![raw::Disc::from_tracks(vec![
+                raw::Track::new("a"),
+                raw::Track::new("b")
+                    .with_artists(vec![Text::from("B")])
+                    .with_genre(Text::from("Jazz")),
+                raw::Track::new("c"),
+            ])]);
+
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let first = disc.track(1).unwrap();
+        assert_eq!(&[Text::from("Various")], first.artists());
+        assert_eq!(Some(&Text::from("Rock")), first.genre());
+
+        let last = disc.track(3).unwrap();
+        assert_eq!(&[Text::from("B")], last.artists());
+        assert_eq!(Some(&Text::from("Jazz")), last.genre());
+        assert_eq!(Some(&[Text::from("Various")][..]), last.album_artists());
+    }
+
+    #[test]
+    fn disc_dates_override_album_dates() {
+        let original = "1997-05-21".parse::<Date>().unwrap();
+        let reissue = "2011-09".parse::<Date>().unwrap();
+        let album = raw::Album::new("foo")
+            .with_date(raw::AlbumDate::Date(reissue))
+            .with_original_date(original)
+            .with_discs(vec![
+                raw::Disc::from_tracks(vec![raw::Track::new("song a 1")]),
+                raw::Disc::from_tracks(vec![raw::Track::new("song b 1")])
+                    .with_date(Date::from_year(2012))
+                    .with_original_date(Date::from_year(1998)),
+            ]);
+
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        assert_eq!(
+            [
+                ("TDRC", "year", Some(reissue)),
+                ("TDRL", "release date", Some(reissue)),
+                ("TDOR", "original release date", Some(original)),
+            ],
+            track.id3_dates()
+        );
+
+        let disc = album.disc(2).unwrap();
+        let track = disc.track(1).unwrap();
+        assert_eq!(Some(Date::from_year(2012)), track.date());
+        assert_eq!(Some(Date::from_year(1998)), track.original_date());
+    }
+
+    #[test]
+    fn sort_names_only_differing_from_names_are_written() {
+        let album = raw::Album::new("The Album")
+            .with_artists(vec![Text::from("Björk")])
+            .with_discs(vec![raw::Disc::from_tracks(vec![raw::Track::new("Song")])]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        let sorts = track.id3_sort_names().map(|(id, _, sort)| (id, sort));
+        assert_eq!(
+            [
+                ("TSOT", None),
+                ("TSOP", None),
+                ("TSO2", None),
+                ("TSOA", Some(String::from("Album, The"))),
+            ],
+            sorts
+        );
+    }
+
+    #[test]
+    fn sort_names_use_album_articles_and_overrides() {
+        use crate::articles::{Articles, Language};
+
+        let album = raw::Album::new("Les Albums")
+            .with_artists(vec![Text::from("The The").with_sort(Some("The The"))])
+            .with_articles(Articles::new(vec![Language::French]))
+            .with_discs(vec![raw::Disc::from_tracks(vec![raw::Track::new(
+                "La Chanson",
+            )
+            .with_artists(Some(vec![
+                Text::from("Björk").with_sort(Some("Guðmundsdóttir, Björk"))
+            ]))])]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        let sorts = track.id3_sort_names().map(|(id, _, sort)| (id, sort));
+        assert_eq!(
+            [
+                ("TSOT", Some(String::from("Chanson, La"))),
+                ("TSOP", Some(String::from("Guðmundsdóttir, Björk"))),
+                ("TSO2", None),
+                ("TSOA", Some(String::from("Albums, Les"))),
+            ],
+            sorts
+        );
+    }
+
+    #[test]
+    fn compilations_always_write_album_artist_sort() {
+        let album = raw::Album::new("The Hits")
+            .with_compilation(true)
+            .with_artists(vec![Text::from(raw::VARIOUS_ARTISTS)])
+            .with_discs(vec![raw::Disc::from_tracks(vec![
+                raw::Track::new("Song").with_artists(vec![Text::from("The Band")])
+            ])]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        let sorts = track.id3_sort_names().map(|(id, _, sort)| (id, sort));
+        assert_eq!(
+            [
+                ("TSOT", None),
+                ("TSOP", Some(String::from("Band, The"))),
+                ("TSO2", Some(String::from(raw::VARIOUS_ARTISTS))),
+                ("TSOA", Some(String::from("Hits, The"))),
+            ],
+            sorts
+        );
+    }
+
+    #[test]
+    fn artists_follow_album_artist_style() {
+        use crate::artists::{ArtistFrames, JoinStyle};
+
+        let album = raw::Album::new("foo")
+            .with_artists(vec![Text::from("a"), Text::from("b")])
+            .with_artist_style(ArtistStyle {
+                join: JoinStyle::Ampersand,
+                frames: ArtistFrames::Multiple,
+            })
+            .with_discs(vec![raw::Disc::from_tracks(vec![raw::Track::new("song")
+                .with_artists(Some(vec![
+                    Text::from("c"),
+                    Text::from("d"),
+                    Text::from("e"),
+                ]))])]);
+        let album = Album::new(album, PathBuf::from("."));
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        assert_eq!("c, d & e", track.artist().value());
+        assert_eq!(Some(String::from("a\0b")), track.album_artist_frame());
+    }
+
+    #[test]
+    fn tracks_with_same_title_dont_share_cached_covers() {
+        let dir = tempfile::tempdir().unwrap();
+        let images = dir.path().join("extras/images");
+        fs::create_dir_all(&images).unwrap();
+        for (name, color) in [("one.png", 0), ("two.png", 255)] {
+            image::RgbImage::from_pixel(4, 4, image::Rgb([color; 3]))
+                .save(images.join(name))
+                .unwrap();
+        }
+
+        let profiles = serde_yaml::from_str("full: {cover: {size: 4}}").unwrap();
+        let album = raw::Album::new("foo")
+            .with_profiles(profiles)
+            .with_discs(vec![
+                raw::Disc::from_tracks(vec![
+                    raw::Track::new("Intro").with_cover(String::from("one.png"))
+                ]),
+                raw::Disc::from_tracks(vec![
+                    raw::Track::new("Intro").with_cover(String::from("two.png"))
+                ]),
+            ]);
+        let album = Album::new(album, dir.path());
+
+        let first = album
+            .disc(1)
+            .unwrap()
+            .track(1)
+            .unwrap()
+            .cover()
+            .unwrap()
+            .cloned();
+        let second = album
+            .disc(2)
+            .unwrap()
+            .track(1)
+            .unwrap()
+            .cover()
+            .unwrap()
+            .cloned();
+        assert!(first.is_some());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn missing_explicit_cover_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let album =
+            raw::Album::new("foo")
+                .with_discs(vec![raw::Disc::from_tracks(vec![
+                    raw::Track::new("song").with_cover(String::from("missing.png"))
+                ])]);
+        let album = Album::new(album, dir.path());
+        let disc = album.disc(1).unwrap();
+        let track = disc.track(1).unwrap();
+        assert!(track.cover().is_err());
+    }
 }
@@ -0,0 +1,651 @@
+//! Transliteration of non-Latin scripts into ASCII.
+//!
+//! [`to_ascii`] runs text through a list of [`Transliterator`]s, and drops anything none of them
+//! handle after decomposing it. Tables for Latin letters, punctuation, Cyrillic, Greek, kana and
+//! Hangul are registered by default, and more can be added with [`register`].
+
+use once_cell::sync::Lazy;
+use std::{borrow::Cow, sync::RwLock};
+use unicode_normalization::UnicodeNormalization;
+
+/// Something that can transliterate the start of a string into ASCII.
+pub trait Transliterator: Send + Sync {
+    /// Transliterate the start of a string.
+    ///
+    /// Returns the ASCII and the number of bytes of `s` that were used, or `None` if the start
+    /// of the string isn't handled.
+    fn transliterate(&self, s: &str) -> Option<(Cow<'static, str>, usize)>;
+}
+
+/// A transliterator that maps single lowercase characters to strings.
+///
+/// Uppercase characters are looked up by their lowercase form and capitalized, and characters
+/// with diacritics fall back to their base character.
+#[derive(Clone, Copy, Debug)]
+pub struct CharTable {
+    table: &'static [(char, &'static str)],
+}
+
+impl CharTable {
+    /// Create a table from pairs of lowercase characters and their ASCII.
+    pub const fn new(table: &'static [(char, &'static str)]) -> Self {
+        Self { table }
+    }
+
+    fn lookup(&self, c: char) -> Option<&'static str> {
+        self.table.iter().find(|(k, _)| *k == c).map(|(_, v)| *v)
+    }
+}
+
+impl Transliterator for CharTable {
+    fn transliterate(&self, s: &str) -> Option<(Cow<'static, str>, usize)> {
+        let mut chars = s.chars();
+        let c = chars.next()?;
+        let lower = c.to_lowercase().next()?;
+        let ascii = self
+            .lookup(lower)
+            .or_else(|| self.lookup(std::iter::once(lower).nfd().next()?))?;
+
+        let ascii = if c == lower {
+            Cow::Borrowed(ascii)
+        } else if chars.next().map(char::is_uppercase).unwrap_or(false) {
+            Cow::Owned(ascii.to_uppercase())
+        } else {
+            let mut rest = ascii.chars();
+            match rest.next() {
+                Some(first) => Cow::Owned(first.to_ascii_uppercase().to_string() + rest.as_str()),
+                None => Cow::Borrowed(ascii),
+            }
+        };
+
+        Some((ascii, c.len_utf8()))
+    }
+}
+
+/// Latin letters that don't decompose into an ASCII letter and a diacritic.
+pub const LATIN: CharTable = CharTable::new(&[
+    ('ß', "ss"),
+    ('æ', "ae"),
+    ('ø', "o"),
+    ('ł', "l"),
+    ('đ', "d"),
+    ('ð', "d"),
+    ('þ', "th"),
+    ('œ', "oe"),
+    ('ı', "i"),
+    ('ȷ', "j"),
+    ('ħ', "h"),
+    ('ŀ', "l"),
+    ('ŋ', "ng"),
+    ('ĸ', "k"),
+    ('ŧ', "t"),
+    ('ſ', "s"),
+    ('ƒ', "f"),
+    ('ƀ', "b"),
+    ('ƈ', "c"),
+    ('ɖ', "d"),
+    ('ɗ', "d"),
+    ('ə', "e"),
+    ('ǝ', "e"),
+    ('ɛ', "e"),
+    ('ɠ', "g"),
+    ('ɨ', "i"),
+    ('ƙ', "k"),
+    ('ƚ', "l"),
+    ('ɲ', "n"),
+    ('ɔ', "o"),
+    ('ƥ', "p"),
+    ('ʠ', "q"),
+    ('ʂ', "s"),
+    ('ƭ', "t"),
+    ('ʉ', "u"),
+    ('ʋ', "v"),
+    ('ƴ', "y"),
+    ('ƶ', "z"),
+    ('ȥ', "z"),
+    ('ʒ', "zh"),
+]);
+
+/// Typographic punctuation and spaces.
+pub const PUNCTUATION: CharTable = CharTable::new(&[
+    ('“', "\""),
+    ('”', "\""),
+    ('„', "\""),
+    ('‟', "\""),
+    ('«', "\""),
+    ('»', "\""),
+    ('‘', "'"),
+    ('’', "'"),
+    ('‚', "'"),
+    ('‛', "'"),
+    ('‹', "'"),
+    ('›', "'"),
+    ('′', "'"),
+    ('ʼ', "'"),
+    ('‐', "-"),
+    ('‑', "-"),
+    ('‒', "-"),
+    ('–', "-"),
+    ('—', "-"),
+    ('―', "-"),
+    ('−', "-"),
+    ('…', "..."),
+    ('•', "-"),
+    ('·', "-"),
+    ('×', "x"),
+    ('÷', "/"),
+    ('⁄', "/"),
+    ('©', "(c)"),
+    ('®', "(R)"),
+    ('\u{a0}', " "),
+    ('\u{2007}', " "),
+    ('\u{2009}', " "),
+    ('\u{202f}', " "),
+]);
+
+/// Russian, Ukrainian, Belarusian, Serbian and Macedonian Cyrillic.
+pub const CYRILLIC: CharTable = CharTable::new(&[
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('д', "d"),
+    ('е', "e"),
+    ('ё', "yo"),
+    ('ж', "zh"),
+    ('з', "z"),
+    ('и', "i"),
+    ('й', "y"),
+    ('к', "k"),
+    ('л', "l"),
+    ('м', "m"),
+    ('н', "n"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('у', "u"),
+    ('ф', "f"),
+    ('х', "kh"),
+    ('ц', "ts"),
+    ('ч', "ch"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', ""),
+    ('ы', "y"),
+    ('ь', ""),
+    ('э', "e"),
+    ('ю', "yu"),
+    ('я', "ya"),
+    ('і', "i"),
+    ('ї', "yi"),
+    ('є', "ye"),
+    ('ґ', "g"),
+    ('ў', "u"),
+    ('ђ', "dj"),
+    ('ј', "j"),
+    ('љ', "lj"),
+    ('њ', "nj"),
+    ('ћ', "c"),
+    ('џ', "dz"),
+    ('ѓ', "gj"),
+    ('ќ', "kj"),
+    ('ѕ', "dz"),
+]);
+
+/// Modern Greek.
+pub const GREEK: CharTable = CharTable::new(&[
+    ('α', "a"),
+    ('β', "v"),
+    ('γ', "g"),
+    ('δ', "d"),
+    ('ε', "e"),
+    ('ζ', "z"),
+    ('η', "i"),
+    ('θ', "th"),
+    ('ι', "i"),
+    ('κ', "k"),
+    ('λ', "l"),
+    ('μ', "m"),
+    ('ν', "n"),
+    ('ξ', "x"),
+    ('ο', "o"),
+    ('π', "p"),
+    ('ρ', "r"),
+    ('σ', "s"),
+    ('ς', "s"),
+    ('τ', "t"),
+    ('υ', "y"),
+    ('φ', "f"),
+    ('χ', "ch"),
+    ('ψ', "ps"),
+    ('ω', "o"),
+]);
+
+/// Hiragana from U+3041 to U+3096, in Hepburn romanization.
+const HIRAGANA: [&str; 86] = [
+    "a", "a", "i", "i", "u", "u", "e", "e", "o", "o", "ka", "ga", "ki", "gi", "ku", "gu", "ke",
+    "ge", "ko", "go", "sa", "za", "shi", "ji", "su", "zu", "se", "ze", "so", "zo", "ta", "da",
+    "chi", "ji", "", "tsu", "zu", "te", "de", "to", "do", "na", "ni", "nu", "ne", "no", "ha", "ba",
+    "pa", "hi", "bi", "pi", "fu", "bu", "pu", "he", "be", "pe", "ho", "bo", "po", "ma", "mi", "mu",
+    "me", "mo", "ya", "ya", "yu", "yu", "yo", "yo", "ra", "ri", "ru", "re", "ro", "wa", "wa", "i",
+    "e", "o", "n", "vu", "ka", "ke",
+];
+
+/// Hiragana and katakana, in Hepburn romanization.
+#[derive(Clone, Copy, Debug)]
+pub struct Kana;
+
+/// A piece of a run of kana.
+enum Syllable {
+    Sound(String),
+    /// A small "tsu", which doubles the next consonant.
+    Sokuon,
+    /// A long vowel mark, which repeats the previous vowel.
+    Long,
+}
+
+impl Kana {
+    /// Maps katakana to the matching hiragana.
+    fn to_hiragana(c: char) -> Option<u32> {
+        match c as u32 {
+            c @ 0x3041..=0x3096 => Some(c),
+            c @ 0x30A1..=0x30F6 => Some(c - 0x60),
+            _ => None,
+        }
+    }
+
+    fn romaji(c: char) -> Option<&'static str> {
+        match c {
+            'ヷ' => Some("va"),
+            'ヸ' => Some("vi"),
+            'ヹ' => Some("ve"),
+            'ヺ' => Some("vo"),
+            _ => Kana::to_hiragana(c).map(|h| HIRAGANA[(h - 0x3041) as usize]),
+        }
+    }
+
+    fn is_small_y(c: char) -> bool {
+        matches!(Kana::to_hiragana(c), Some(0x3083 | 0x3085 | 0x3087))
+    }
+
+    fn is_small_vowel(c: char) -> bool {
+        matches!(
+            Kana::to_hiragana(c),
+            Some(0x3041 | 0x3043 | 0x3045 | 0x3047 | 0x3049)
+        )
+    }
+
+    fn syllables(run: &str) -> Vec<Syllable> {
+        let mut syllables = Vec::new();
+        let mut chars = run.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == 'ー' {
+                syllables.push(Syllable::Long);
+                continue;
+            }
+            if matches!(Kana::to_hiragana(c), Some(0x3063)) {
+                syllables.push(Syllable::Sokuon);
+                continue;
+            }
+
+            let base = Kana::romaji(c).unwrap_or("");
+            let mut sound = String::from(base);
+            match chars.peek() {
+                // Combinations like "kya" and "sha".
+                Some(&next) if Kana::is_small_y(next) && base.len() > 1 && base.ends_with('i') => {
+                    let small = Kana::romaji(next).unwrap_or("");
+                    sound.pop();
+                    if matches!(base, "shi" | "chi" | "ji") {
+                        sound.push_str(&small[1..]);
+                    } else {
+                        sound.push_str(small);
+                    }
+                    chars.next();
+                }
+                // Combinations used in loanwords like "fa" and "ti".
+                Some(&next) if Kana::is_small_vowel(next) && !Kana::is_small_vowel(c) => {
+                    let small = Kana::romaji(next).unwrap_or("");
+                    if base == "u" {
+                        sound = format!("w{}", small);
+                    } else if base.len() > 1 {
+                        sound.pop();
+                        sound.push_str(small);
+                    } else {
+                        sound.push_str(small);
+                    }
+                    chars.next();
+                }
+                _ => {}
+            }
+            syllables.push(Syllable::Sound(sound));
+        }
+
+        syllables
+    }
+
+    fn romanize(run: &str) -> String {
+        let syllables = Kana::syllables(run);
+        let mut out = String::new();
+
+        for (i, syllable) in syllables.iter().enumerate() {
+            let next = match syllables.get(i + 1) {
+                Some(Syllable::Sound(next)) => Some(next.as_str()),
+                _ => None,
+            };
+
+            match syllable {
+                Syllable::Sound(sound) => {
+                    out.push_str(sound);
+                    // Separate "n" from a following vowel, so "kan'i" isn't read as "ka-ni".
+                    let is_ambiguous = next
+                        .and_then(|n| n.chars().next())
+                        .map(|c| "aeiouy".contains(c))
+                        .unwrap_or(false);
+                    if sound == "n" && is_ambiguous {
+                        out.push('\'');
+                    }
+                }
+                Syllable::Sokuon => match next {
+                    Some(next) if next.starts_with("ch") => out.push('t'),
+                    Some(next) => {
+                        if let Some(c) = next.chars().next().filter(|c| !"aeiou".contains(*c)) {
+                            out.push(c);
+                        }
+                    }
+                    None => {}
+                },
+                Syllable::Long => {
+                    if let Some(vowel) = out.chars().rev().find(|c| "aeiou".contains(*c)) {
+                        out.push(vowel);
+                    }
+                }
+            }
+        }
+
+        out
+    }
+}
+
+impl Transliterator for Kana {
+    fn transliterate(&self, s: &str) -> Option<(Cow<'static, str>, usize)> {
+        let is_kana = |c: char| c == 'ー' || Kana::romaji(c).is_some();
+
+        // Convert whole runs at a time, since kana combine with their neighbors.
+        let first = s.chars().next()?;
+        if !is_kana(first) || first == 'ー' {
+            return None;
+        }
+        let len = s
+            .char_indices()
+            .find(|(_, c)| !is_kana(*c))
+            .map(|(i, _)| i)
+            .unwrap_or(s.len());
+
+        Some((Cow::Owned(Kana::romanize(&s[..len])), len))
+    }
+}
+
+/// Hangul syllables, in Revised Romanization.
+#[derive(Clone, Copy, Debug)]
+pub struct Hangul;
+
+const HANGUL_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p",
+    "h",
+];
+
+const HANGUL_VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we",
+    "wi", "yu", "eu", "ui", "i",
+];
+
+const HANGUL_FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p",
+    "t", "t", "ng", "t", "t", "k", "t", "p", "t",
+];
+
+impl Transliterator for Hangul {
+    fn transliterate(&self, s: &str) -> Option<(Cow<'static, str>, usize)> {
+        let c = s.chars().next()?;
+        let index = (c as u32).checked_sub(0xAC00).filter(|i| *i < 11172)? as usize;
+
+        let initial = HANGUL_INITIALS[index / 588];
+        let vowel = HANGUL_VOWELS[(index % 588) / 28];
+        let last = HANGUL_FINALS[index % 28];
+        Some((format!("{}{}{}", initial, vowel, last).into(), c.len_utf8()))
+    }
+}
+
+/// The transliterators registered by default, in order of priority.
+fn builtin() -> Vec<Box<dyn Transliterator>> {
+    vec![
+        Box::new(LATIN),
+        Box::new(PUNCTUATION),
+        Box::new(CYRILLIC),
+        Box::new(GREEK),
+        Box::new(Kana),
+        Box::new(Hangul),
+    ]
+}
+
+static TRANSLITERATORS: Lazy<RwLock<Vec<Box<dyn Transliterator>>>> =
+    Lazy::new(|| RwLock::new(builtin()));
+
+/// Register a transliterator, which takes priority over the ones already registered.
+///
+/// This only affects `Text`s created afterwards.
+pub fn register<T: Transliterator + 'static>(transliterator: T) {
+    TRANSLITERATORS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(0, Box::new(transliterator));
+}
+
+/// Convert a character that no transliterator handles into ASCII, if possible.
+fn fold_char(c: char, out: &mut String) {
+    out.extend(std::iter::once(c).nfkd().filter(char::is_ascii));
+}
+
+/// Convert text into ASCII using the registered transliterators.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::translit::to_ascii;
+/// assert_eq!("Moskva", to_ascii("Москва"));
+/// assert_eq!("Athina", to_ascii("Αθήνα"));
+/// assert_eq!("sakura", to_ascii("さくら"));
+/// assert_eq!("seoul", to_ascii("서울"));
+/// ```
+pub fn to_ascii(s: &str) -> String {
+    let transliterators = TRANSLITERATORS.read().unwrap_or_else(|e| e.into_inner());
+    to_ascii_with(s, &transliterators)
+}
+
+/// Convert text into ASCII using the given transliterators, in order of priority.
+fn to_ascii_with(s: &str, transliterators: &[Box<dyn Transliterator>]) -> String {
+    // Compose first, so kana and hangul aren't split apart, and full-width forms are folded.
+    let s: String = s.nfkc().collect();
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s.as_str();
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii() {
+            out.push(c);
+            rest = &rest[1..];
+            continue;
+        }
+
+        match transliterators
+            .iter()
+            .find_map(|t| t.transliterate(rest).filter(|(_, len)| *len > 0))
+        {
+            Some((ascii, len)) => {
+                out.push_str(&ascii);
+                rest = &rest[len..];
+            }
+            None => {
+                fold_char(c, &mut out);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::{Arbitrary, Gen};
+    use quickcheck_macros::quickcheck;
+
+    /// A string made of ASCII and characters from a table.
+    #[derive(Clone, Debug)]
+    struct TableString(String);
+
+    impl TableString {
+        fn arbitrary_from(g: &mut Gen, table: &CharTable) -> Self {
+            let chars: Vec<char> = table.table.iter().map(|(c, _)| *c).collect();
+            let s = Vec::<bool>::arbitrary(g)
+                .into_iter()
+                .map(|from_table| {
+                    if from_table {
+                        *g.choose(&chars).unwrap()
+                    } else {
+                        *g.choose(&['a', 'Z', ' ', '1']).unwrap()
+                    }
+                })
+                .collect();
+            TableString(s)
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct LatinString(TableString);
+
+    impl Arbitrary for LatinString {
+        fn arbitrary(g: &mut Gen) -> Self {
+            LatinString(TableString::arbitrary_from(g, &LATIN))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct PunctuationString(TableString);
+
+    impl Arbitrary for PunctuationString {
+        fn arbitrary(g: &mut Gen) -> Self {
+            PunctuationString(TableString::arbitrary_from(g, &PUNCTUATION))
+        }
+    }
+
+    #[quickcheck]
+    fn output_is_ascii(s: String) -> bool {
+        to_ascii(&s).is_ascii()
+    }
+
+    #[quickcheck]
+    fn is_idempotent(s: String) -> bool {
+        let ascii = to_ascii(&s);
+        to_ascii(&ascii) == ascii
+    }
+
+    #[quickcheck]
+    fn latin_letters_arent_dropped(s: LatinString) -> bool {
+        let TableString(s) = s.0;
+        to_ascii(&s).len() >= s.chars().count()
+    }
+
+    #[quickcheck]
+    fn latin_letters_keep_their_case(s: LatinString) -> bool {
+        let TableString(s) = s.0;
+        let ascii = to_ascii(&s);
+        let starts_upper = |s: &str| s.chars().next().map(char::is_uppercase);
+        starts_upper(&s) == starts_upper(&ascii)
+    }
+
+    #[quickcheck]
+    fn punctuation_isnt_dropped(s: PunctuationString) -> bool {
+        let TableString(s) = s.0;
+        to_ascii(&s).len() >= s.chars().count()
+    }
+
+    #[test]
+    fn folds_latin_letters() {
+        assert_eq!("Mo", to_ascii("Mø"));
+        assert_eq!("Aeroskobing", to_ascii("Ærøskøbing"));
+        assert_eq!("Lodz", to_ascii("Łódź"));
+        assert_eq!("Strasse", to_ascii("Straße"));
+        assert_eq!("STRASSE", to_ascii("STRAẞE"));
+        assert_eq!("Dordevic", to_ascii("Đorđević"));
+        assert_eq!("Thorr", to_ascii("Þórr"));
+        assert_eq!("oeuvre", to_ascii("œuvre"));
+    }
+
+    #[test]
+    fn folds_punctuation() {
+        assert_eq!("A - B", to_ascii("A – B"));
+        assert_eq!("A-B", to_ascii("A—B"));
+        assert_eq!("Wait...", to_ascii("Wait…"));
+        assert_eq!("a b", to_ascii("a\u{a0}b"));
+        assert_eq!("\"Hi\" 'there'", to_ascii("„Hi“ ‹there›"));
+    }
+
+    #[test]
+    fn transliterates_cyrillic() {
+        assert_eq!("Shchedrin", to_ascii("Щедрин"));
+        assert_eq!("Zhanna Aguzarova", to_ascii("Жанна Агузарова"));
+        assert_eq!("DDT", to_ascii("ДДТ"));
+        assert_eq!("Kino - Gruppa krovi", to_ascii("Кино - Группа крови"));
+    }
+
+    #[test]
+    fn transliterates_greek() {
+        assert_eq!("Mikis Theodorakis", to_ascii("Μίκης Θεοδωράκης"));
+    }
+
+    #[test]
+    fn transliterates_hiragana_and_katakana() {
+        assert_eq!("sakura", to_ascii("さくら"));
+        assert_eq!("kitto", to_ascii("きっと"));
+        assert_eq!("shashin", to_ascii("しゃしん"));
+        assert_eq!("matcha", to_ascii("まっちゃ"));
+        assert_eq!("kon'ya", to_ascii("こんや"));
+        assert_eq!("koohii", to_ascii("コーヒー"));
+        assert_eq!("faito", to_ascii("ファイト"));
+        assert_eq!("ga", to_ascii("ｶﾞ"));
+    }
+
+    #[test]
+    fn transliterates_hangul() {
+        assert_eq!("hangeul", to_ascii("한글"));
+        assert_eq!("Bangtan sonyeondan", to_ascii("Bangtan 소년단"));
+    }
+
+    #[test]
+    fn drops_unhandled_characters() {
+        assert_eq!("", to_ascii("本"));
+        assert_eq!("Sigur Ros", to_ascii("Sigur Rós"));
+    }
+
+    #[test]
+    fn registered_transliterators_take_priority() {
+        struct Book;
+
+        impl Transliterator for Book {
+            fn transliterate(&self, s: &str) -> Option<(Cow<'static, str>, usize)> {
+                s.starts_with('冊')
+                    .then_some((Cow::Borrowed("book"), '冊'.len_utf8()))
+            }
+        }
+
+        // A local list, so the global one other tests use is left alone.
+        let mut transliterators = builtin();
+        transliterators.insert(0, Box::new(Book));
+        assert_eq!("a book", to_ascii_with("a 冊", &transliterators));
+        assert_eq!("a ", to_ascii("a 冊"));
+    }
+}
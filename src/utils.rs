@@ -1,4 +1,4 @@
-use crate::Text;
+use crate::{articles::Articles, Text};
 use std::borrow::Cow;
 
 /// Get the number of base 10 digits in a number.
@@ -29,68 +29,32 @@ pub fn num_digits(mut number: usize) -> usize {
 /// let text = [Text::from("foo"), Text::from(("bar", "baar")), Text::from("baz")];
 /// assert_eq!(Cow::Owned::<Text>(Text::from(("foo, bar, baz", "foo, baar, baz"))), comma_separated(&text[..]));
 /// ```
-pub fn comma_separated(text: &[Text]) -> Cow<Text> {
-    use crate::text::{COMMA_SEP, EMPTY_TEXT};
+pub fn comma_separated(text: &[Text]) -> Cow<'_, Text> {
+    use crate::text::COMMA_SEP;
 
     if text.len() == 1 {
         Cow::Borrowed(&text[0])
     } else {
-        let mut res = EMPTY_TEXT;
-        for (i, t) in text.iter().enumerate() {
-            if i != 0 {
-                res += COMMA_SEP;
-            }
-            res += t;
-        }
-        Cow::Owned(res)
-    }
-}
-
-macro_rules! expect_char {
-    ($cs:expr, $( $c:literal ),*) => {
-        let next = $cs.next()?;
-        if $( next != $c )&&* {
-            return None;
-        }
+        Cow::Owned(Text::join(text, &COMMA_SEP))
     }
 }
 
 /// Splits an initial article from a string.
 ///
 /// Returns a pair of the article and the rest of the string, or None if the string didn't start
-/// with an article.
+/// with an article, or is only an article.
 ///
 /// Articles are "a", "an", and "the", ignoring case.
 ///
 /// ```rust
+/// # #![allow(deprecated)]
 /// # use maestro::utils::split_article;
 /// assert_eq!(split_article("A Thing"), Some(("A", "Thing")));
 /// assert_eq!(split_article("Another Thing"), None);
 /// ```
+#[deprecated(note = "use `Articles::split`, which knows articles in other languages")]
 pub fn split_article(s: &str) -> Option<(&str, &str)> {
-    let mut cs = s.chars();
-
-    match cs.next()? {
-        't' | 'T' => {
-            expect_char!(cs, 'h', 'H');
-            expect_char!(cs, 'e', 'E');
-            expect_char!(cs, ' ');
-            unsafe { Some((s.get_unchecked(..3), s.get_unchecked(4..))) }
-        }
-        'a' | 'A' => {
-            let next = cs.next()?;
-            if next == ' ' {
-                return unsafe { Some((s.get_unchecked(..1), s.get_unchecked(2..))) };
-            }
-            if next != 'n' && next != 'N' {
-                return None;
-            }
-            expect_char!(cs, ' ');
-
-            unsafe { Some((s.get_unchecked(..2), s.get_unchecked(3..))) }
-        }
-        _ => None,
-    }
+    Articles::default().split(s)
 }
 
 const FILE_UNSAFE_CHARS: [char; 10] = ['<', '>', ':', '"', '/', '|', '~', '\\', '*', '?'];
@@ -245,21 +209,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn split_article_preserves_capitalization() {
-        assert_eq!(split_article("THe titLe"), Some(("THe", "titLe")));
-    }
-
-    #[test]
-    fn split_article_only_removes_first_space() {
-        assert_eq!(split_article("the   title"), Some(("the", "  title")));
-    }
-
-    #[test]
-    fn split_article_doesnt_split_if_no_space() {
-        assert_eq!(split_article("the_title"), None);
-    }
-
     mod make_file_safe {
         use super::{is_file_safe, make_file_safe};
         use quickcheck::TestResult;
@@ -0,0 +1,99 @@
+//! Classical works made of movements, like a symphony.
+
+use crate::Text;
+
+/// Where a track sits in the work it's a movement of.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Movement {
+    /// The work the movement is part of.
+    pub work: Text,
+
+    /// The movement's number within the work, starting at 1.
+    pub number: usize,
+
+    /// The number of movements in the work.
+    pub count: usize,
+}
+
+impl Movement {
+    /// Gets the movement's number as an uppercase roman numeral, as movements are usually
+    /// numbered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use maestro::{work::Movement, Text};
+    /// let movement = Movement {
+    ///     work: Text::from("Symphony No. 9"),
+    ///     number: 2,
+    ///     count: 4,
+    /// };
+    /// assert_eq!("II", movement.numeral());
+    /// ```
+    pub fn numeral(&self) -> String {
+        roman_numeral(self.number)
+    }
+
+    /// Gets the contents of the MVIN frame, like "2/4".
+    pub fn frame_value(&self) -> String {
+        format!("{}/{}", self.number, self.count)
+    }
+}
+
+/// Writes a number as an uppercase roman numeral, or in digits if it's 0 or too big to write
+/// sensibly.
+///
+/// # Examples
+///
+/// ```rust
+/// # use maestro::work::roman_numeral;
+/// assert_eq!("IV", roman_numeral(4));
+/// assert_eq!("XIV", roman_numeral(14));
+/// assert_eq!("0", roman_numeral(0));
+/// ```
+pub fn roman_numeral(number: usize) -> String {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    if number == 0 || number >= 4000 {
+        return number.to_string();
+    }
+
+    let mut rest = number;
+    let mut numeral = String::new();
+    for (value, letters) in NUMERALS {
+        while rest >= value {
+            numeral.push_str(letters);
+            rest -= value;
+        }
+    }
+    numeral
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numerals_are_subtractive() {
+        let numerals = (1..=10).map(roman_numeral).collect::<Vec<_>>();
+        assert_eq!(
+            vec!["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X"],
+            numerals
+        );
+        assert_eq!("MCMXCIX", roman_numeral(1999));
+    }
+}